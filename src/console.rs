@@ -0,0 +1,323 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use bevy::{
+    ecs::system::SystemId,
+    input::{
+        ButtonState, InputSystem,
+        keyboard::{Key, KeyboardInput},
+    },
+    input_focus::{InputFocus, InputFocusSet},
+    prelude::*,
+};
+
+const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+const MAX_LOG_LINES: usize = 12;
+const MAX_HISTORY: usize = 50;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ConsoleCommands>();
+    app.init_resource::<Console>();
+    app.add_console_command("help", "help - list commands", help);
+
+    app.add_systems(
+        PreUpdate,
+        (
+            toggle_console,
+            block_game_input.run_if(|console: Res<Console>| console.open),
+        )
+            .chain()
+            .after(InputSystem)
+            .before(InputFocusSet::Dispatch),
+    );
+    app.add_systems(
+        Update,
+        (
+            (edit_console_line, run_pending_commands, update_console_text)
+                .chain()
+                .run_if(|console: Res<Console>| console.open),
+            restore_input_focus
+                .run_if(|console: Res<Console>| !console.open && console.stashed_focus.is_some()),
+        ),
+    );
+}
+
+pub type ConsoleArgs = Vec<String>;
+pub type ConsoleResult = Result<String, String>;
+
+struct ConsoleCommand {
+    usage: String,
+    system: SystemId<In<ConsoleArgs>, ConsoleResult>,
+}
+
+#[derive(Resource, Default)]
+struct ConsoleCommands(BTreeMap<String, ConsoleCommand>);
+
+pub trait ConsoleExt {
+    fn add_console_command<M>(
+        &mut self,
+        name: &str,
+        usage: &str,
+        system: impl IntoSystem<In<ConsoleArgs>, ConsoleResult, M> + 'static,
+    ) -> &mut Self;
+}
+
+impl ConsoleExt for App {
+    fn add_console_command<M>(
+        &mut self,
+        name: &str,
+        usage: &str,
+        system: impl IntoSystem<In<ConsoleArgs>, ConsoleResult, M> + 'static,
+    ) -> &mut Self {
+        let world = self.world_mut();
+        let system = world.register_system(system);
+        world.get_resource_or_init::<ConsoleCommands>().0.insert(
+            name.to_string(),
+            ConsoleCommand {
+                usage: usage.to_string(),
+                system,
+            },
+        );
+        self
+    }
+}
+
+pub fn parse_arg<T: std::str::FromStr>(
+    args: &[String],
+    index: usize,
+    name: &str,
+) -> Result<T, String> {
+    let arg = args
+        .get(index)
+        .ok_or_else(|| format!("missing argument <{name}>"))?;
+    arg.parse().map_err(|_| format!("invalid <{name}>: {arg}"))
+}
+
+#[derive(Resource, Default, Debug)]
+struct Console {
+    open: bool,
+    line: String,
+    log: VecDeque<String>,
+    history: Vec<String>,
+    history_index: Option<usize>,
+    pending: Vec<String>,
+    stashed_focus: Option<Entity>,
+}
+
+impl Console {
+    fn print(&mut self, line: impl Into<String>) {
+        for line in line.into().lines() {
+            if self.log.len() == MAX_LOG_LINES {
+                self.log.pop_front();
+            }
+            self.log.push_back(line.to_string());
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+struct ConsoleText;
+
+fn help(_: In<ConsoleArgs>, commands: Res<ConsoleCommands>) -> ConsoleResult {
+    Ok(commands
+        .0
+        .values()
+        .map(|command| command.usage.as_str())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn block_game_input(
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut input_focus: ResMut<InputFocus>,
+    mut console: ResMut<Console>,
+) {
+    keyboard_input.reset_all();
+    if let Some(entity) = input_focus.get() {
+        console.stashed_focus = Some(entity);
+        input_focus.clear();
+    }
+}
+
+fn restore_input_focus(
+    mut commands: Commands,
+    mut input_focus: ResMut<InputFocus>,
+    mut console: ResMut<Console>,
+) {
+    if let Some(entity) = console.stashed_focus.take()
+        && input_focus.get().is_none()
+        && commands.get_entity(entity).is_ok()
+    {
+        input_focus.set(entity);
+    }
+}
+
+fn toggle_console(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut console: ResMut<Console>,
+    text: Query<Entity, With<ConsoleText>>,
+) {
+    let toggled = keyboard_events
+        .read()
+        .any(|event| event.key_code == TOGGLE_KEY && event.state == ButtonState::Pressed);
+    if !toggled {
+        return;
+    }
+
+    console.open = !console.open;
+    if !console.open {
+        for entity in &text {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    commands.spawn((
+        ConsoleText,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Px(0.0),
+            padding: UiRect::all(Val::Px(10.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        GlobalZIndex(3),
+        Text::default(),
+        TextFont::from_font_size(18.0),
+        TextColor(Color::srgb_u8(0xe0, 0xe0, 0xe0)),
+    ));
+}
+
+fn complete(console: &mut Console, commands: &ConsoleCommands) {
+    if console.line.contains(' ') {
+        return;
+    }
+
+    let matches = commands
+        .0
+        .keys()
+        .filter(|name| name.starts_with(&console.line))
+        .collect::<Vec<_>>();
+    match matches.as_slice() {
+        [] => {}
+        [name] => console.line = format!("{name} "),
+        [first, rest @ ..] => {
+            let common = rest.iter().fold(first.len(), |length, name| {
+                first
+                    .chars()
+                    .zip(name.chars())
+                    .take(length)
+                    .take_while(|(a, b)| a == b)
+                    .count()
+            });
+            let candidates = matches
+                .iter()
+                .map(|name| name.as_str())
+                .collect::<Vec<_>>()
+                .join("  ");
+            console.line = first[..common].to_string();
+            console.print(candidates);
+        }
+    }
+}
+
+fn browse_history(console: &mut Console, older: bool) {
+    if console.history.is_empty() {
+        return;
+    }
+
+    let last = console.history.len() - 1;
+    console.history_index = match (console.history_index, older) {
+        (None, true) => Some(last),
+        (None, false) => None,
+        (Some(index), true) => Some(index.saturating_sub(1)),
+        (Some(index), false) if index < last => Some(index + 1),
+        (Some(_), false) => None,
+    };
+    console.line = console
+        .history_index
+        .map(|index| console.history[index].clone())
+        .unwrap_or_default();
+}
+
+fn edit_console_line(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut console: ResMut<Console>,
+    commands: Res<ConsoleCommands>,
+) {
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed || event.key_code == TOGGLE_KEY {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut console.line);
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                if console.history.last().is_none_or(|last| last != line) {
+                    if console.history.len() == MAX_HISTORY {
+                        console.history.remove(0);
+                    }
+                    console.history.push(line.to_string());
+                }
+                console.history_index = None;
+                console.pending.push(line.to_string());
+            }
+            Key::Backspace => {
+                console.line.pop();
+            }
+            Key::Tab => complete(&mut console, &commands),
+            Key::ArrowUp => browse_history(&mut console, true),
+            Key::ArrowDown => browse_history(&mut console, false),
+            _ => {
+                if let Some(typed) = &event.text {
+                    let typed = typed
+                        .chars()
+                        .filter(|character| !character.is_control())
+                        .collect::<String>();
+                    console.line.push_str(&typed);
+                }
+            }
+        }
+    }
+}
+
+fn run_pending_commands(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<Console>().pending);
+    for line in pending {
+        let mut words = line.split_whitespace().map(str::to_string);
+        let name = words.next().unwrap_or_default();
+        let args = words.collect::<ConsoleArgs>();
+        let command = world
+            .resource::<ConsoleCommands>()
+            .0
+            .get(&name)
+            .map(|command| (command.system, command.usage.clone()));
+
+        let output = match command {
+            None => Err(format!("unknown command: {name} (try help)")),
+            Some((system, usage)) => match world.run_system_with(system, args) {
+                Ok(Ok(output)) => Ok(output),
+                Ok(Err(error)) => Err(format!("{error}\nusage: {usage}")),
+                Err(error) => Err(error.to_string()),
+            },
+        };
+
+        let mut console = world.resource_mut::<Console>();
+        console.print(format!("> {line}"));
+        match output {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => console.print(output),
+            Err(error) => console.print(format!("error: {error}")),
+        }
+    }
+}
+
+fn update_console_text(mut text: Single<&mut Text, With<ConsoleText>>, console: Res<Console>) {
+    let log = console.log.iter().cloned().collect::<Vec<_>>().join("\n");
+    text.0 = format!("{log}\n> {}_", console.line);
+}
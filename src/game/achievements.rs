@@ -0,0 +1,247 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        GameState, GameSystems, RestartRun,
+        events::{BoardCleared, FoodEaten, LengthChanged},
+        level::{Level, Rules},
+    },
+    profiles::{ActiveProfile, load_profile_data},
+    settings::Difficulty,
+    storage,
+};
+
+const ACHIEVEMENTS_FILE: &str = "achievements.json";
+const TOAST_SECONDS: f32 = 3.0;
+const FOOD_WINDOW_SECONDS: f32 = 60.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<AchievementRegistry>();
+    app.init_resource::<AchievementProgress>();
+    app.add_systems(
+        PreUpdate,
+        load_profile_data::<AchievementProgress>(ACHIEVEMENTS_FILE)
+            .run_if(resource_changed::<ActiveProfile>),
+    );
+    app.init_resource::<RunStats>();
+
+    app.add_systems(
+        OnExit(GameState::None),
+        reset_run_stats.in_set(GameSystems::Spawn),
+    );
+
+    app.add_systems(RestartRun, reset_run_stats);
+
+    app.add_systems(
+        Update,
+        (
+            count_survival_time.run_if(in_state(GameState::Run)),
+            record_run_stats,
+            unlock_achievements,
+        )
+            .chain()
+            .in_set(GameSystems::Update),
+    );
+
+    app.add_systems(
+        OnEnter(GameState::Over),
+        save_progress.run_if(resource_changed::<AchievementProgress>),
+    );
+    app.add_systems(
+        OnEnter(GameState::None),
+        save_progress.run_if(resource_changed::<AchievementProgress>),
+    );
+
+    app.add_systems(Update, expire_toasts);
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AchievementCondition {
+    ReachLength {
+        length: usize,
+    },
+    EatWithin {
+        food: String,
+        count: usize,
+        seconds: f32,
+    },
+    ClearBoard,
+    Survive {
+        seconds: f32,
+        difficulty: Option<Difficulty>,
+    },
+}
+
+impl AchievementCondition {
+    pub fn target(&self) -> u32 {
+        match self {
+            AchievementCondition::ReachLength { length } => *length as u32,
+            AchievementCondition::EatWithin { count, .. } => *count as u32,
+            AchievementCondition::ClearBoard => 1,
+            AchievementCondition::Survive { seconds, .. } => *seconds as u32,
+        }
+    }
+
+    fn progress(&self, stats: &RunStats, rules: &Rules) -> u32 {
+        match self {
+            AchievementCondition::ReachLength { .. } => stats.length as u32,
+            AchievementCondition::EatWithin { food, seconds, .. } => stats
+                .eaten
+                .iter()
+                .filter(|(time, kind)| kind == food && stats.seconds - time <= *seconds)
+                .count()
+                as u32,
+            AchievementCondition::ClearBoard => u32::from(stats.board_cleared),
+            AchievementCondition::Survive {
+                difficulty: Some(difficulty),
+                ..
+            } if *difficulty != rules.difficulty => 0,
+            AchievementCondition::Survive { .. } => stats.seconds as u32,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AchievementDefinition {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub condition: AchievementCondition,
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct AchievementRegistry {
+    pub achievements: Vec<AchievementDefinition>,
+}
+
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct AchievementProgress {
+    pub unlocked: BTreeSet<String>,
+    pub best: HashMap<String, u32>,
+}
+
+#[derive(Resource, Default, Debug)]
+struct RunStats {
+    seconds: f32,
+    length: usize,
+    eaten: VecDeque<(f32, String)>,
+    board_cleared: bool,
+}
+
+#[derive(Component, Debug)]
+struct Toast(Timer);
+
+fn toast(text: String, index: usize) -> impl Bundle {
+    (
+        Toast(Timer::from_seconds(TOAST_SECONDS, TimerMode::Once)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0 + index as f32 * 60.0),
+            right: Val::Px(10.0),
+            padding: UiRect::axes(Val::Px(20.0), Val::Px(10.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        GlobalZIndex(1),
+        children![(
+            Text::new(text),
+            TextFont::from_font_size(30.0),
+            TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+        )],
+    )
+}
+
+fn reset_run_stats(mut stats: ResMut<RunStats>) {
+    *stats = RunStats::default();
+}
+
+fn count_survival_time(time: Res<Time>, mut stats: ResMut<RunStats>) {
+    stats.seconds += time.delta_secs();
+}
+
+fn record_run_stats(
+    mut stats: ResMut<RunStats>,
+    mut food_eaten: EventReader<FoodEaten>,
+    mut length_changed: EventReader<LengthChanged>,
+    mut board_cleared: EventReader<BoardCleared>,
+) {
+    for event in food_eaten.read() {
+        let seconds = stats.seconds;
+        stats.eaten.push_back((seconds, event.kind.clone()));
+    }
+    while stats
+        .eaten
+        .front()
+        .is_some_and(|(time, _)| stats.seconds - time > FOOD_WINDOW_SECONDS)
+    {
+        stats.eaten.pop_front();
+    }
+    for event in length_changed.read() {
+        stats.length = stats.length.max(event.length);
+    }
+    if board_cleared.read().count() > 0 {
+        stats.board_cleared = true;
+    }
+}
+
+fn unlock_achievements(
+    mut commands: Commands,
+    mut progress: ResMut<AchievementProgress>,
+    registry: Res<AchievementRegistry>,
+    stats: Res<RunStats>,
+    level: Res<Level>,
+    toasts: Query<(), With<Toast>>,
+    profile: Res<ActiveProfile>,
+) {
+    let mut toast_index = toasts.iter().count();
+    let mut unlocked_any = false;
+
+    for achievement in &registry.achievements {
+        if progress.unlocked.contains(&achievement.id) {
+            continue;
+        }
+
+        let current = achievement.condition.progress(&stats, &level.rules);
+        let best = progress
+            .best
+            .get(&achievement.id)
+            .copied()
+            .unwrap_or_default();
+        if current > best {
+            progress.best.insert(achievement.id.clone(), current);
+        }
+
+        if current >= achievement.condition.target() {
+            progress.unlocked.insert(achievement.id.clone());
+            commands.spawn(toast(
+                format!("Unlocked: {}", achievement.name),
+                toast_index,
+            ));
+            toast_index += 1;
+            unlocked_any = true;
+        }
+    }
+
+    if unlocked_any && profile.is_some() {
+        storage::save(**profile, ACHIEVEMENTS_FILE, &*progress);
+    }
+}
+
+fn save_progress(progress: Res<AchievementProgress>, profile: Res<ActiveProfile>) {
+    if profile.is_some() {
+        storage::save(**profile, ACHIEVEMENTS_FILE, &*progress);
+    }
+}
+
+fn expire_toasts(mut commands: Commands, mut toasts: Query<(Entity, &mut Toast)>, time: Res<Time>) {
+    for (entity, mut toast) in &mut toasts {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
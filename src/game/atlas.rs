@@ -1,16 +1,23 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::game::{GameState, GameSystems};
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<SpriteAtlas>();
+    app.init_resource::<AtlasMetadata>();
+    app.add_systems(
+        Update,
+        update_atlas_layout.run_if(resource_changed::<AtlasMetadata>),
+    );
     app.add_systems(
         OnExit(GameState::None),
         setup_texture_atlas.in_set(GameSystems::Resources),
     );
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
 pub enum AtlasSprite {
     Head1 = 0,
     Head2 = 1,
@@ -30,6 +37,40 @@ impl From<AtlasSprite> for usize {
     }
 }
 
+#[derive(Resource, Asset, TypePath, Clone, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct AtlasMetadata {
+    pub tile_size: UVec2,
+    pub columns: u32,
+    pub rows: u32,
+    pub padding: Option<UVec2>,
+    pub offset: Option<UVec2>,
+}
+
+impl Default for AtlasMetadata {
+    fn default() -> Self {
+        Self {
+            tile_size: UVec2::splat(16),
+            columns: 2,
+            rows: 7,
+            padding: None,
+            offset: None,
+        }
+    }
+}
+
+impl AtlasMetadata {
+    fn layout(&self) -> TextureAtlasLayout {
+        TextureAtlasLayout::from_grid(
+            self.tile_size,
+            self.columns,
+            self.rows,
+            self.padding,
+            self.offset,
+        )
+    }
+}
+
 #[derive(Resource, Default, Debug)]
 pub struct SpriteAtlas {
     pub image: Handle<Image>,
@@ -37,12 +78,17 @@ pub struct SpriteAtlas {
 }
 
 pub fn atlas_sprite(atlas: &SpriteAtlas, index: usize) -> impl Bundle {
+    tinted_atlas_sprite(atlas, index, Color::WHITE)
+}
+
+pub fn tinted_atlas_sprite(atlas: &SpriteAtlas, index: usize, color: Color) -> impl Bundle {
     (Sprite {
         image: atlas.image.clone(),
         texture_atlas: Some(TextureAtlas {
             layout: atlas.layout.clone(),
             index,
         }),
+        color,
         custom_size: Some(Vec2::ONE),
         ..default()
     },)
@@ -52,13 +98,18 @@ fn setup_texture_atlas(
     asset_server: Res<AssetServer>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     mut atlas: ResMut<SpriteAtlas>,
+    metadata: Res<AtlasMetadata>,
 ) {
     atlas.image = asset_server.load("atlas.png");
-    atlas.layout = texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
-        UVec2::splat(16),
-        2,
-        7,
-        None,
-        None,
-    ));
+    atlas.layout = texture_atlas_layouts.add(metadata.layout());
+}
+
+fn update_atlas_layout(
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    atlas: Res<SpriteAtlas>,
+    metadata: Res<AtlasMetadata>,
+) {
+    if texture_atlas_layouts.contains(&atlas.layout) {
+        texture_atlas_layouts.insert(&atlas.layout, metadata.layout());
+    }
 }
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        GameMode, GameState, GameSystems, RestartRun,
+        events::{CatCrashed, FoodEaten, GoalReached, LengthChanged},
+    },
+    profiles::{ActiveProfile, load_profile_data},
+    storage,
+};
+
+const CAMPAIGN_FILE: &str = "campaign.json";
+pub const MAX_STARS: u32 = 3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Campaign>();
+    app.init_resource::<CampaignStage>();
+    app.init_resource::<CampaignProgress>();
+    app.add_systems(
+        PreUpdate,
+        load_profile_data::<CampaignProgress>(CAMPAIGN_FILE)
+            .run_if(resource_changed::<ActiveProfile>),
+    );
+    app.init_resource::<CampaignRun>();
+
+    app.add_systems(
+        OnExit(GameState::None),
+        reset_campaign_run.in_set(GameSystems::Spawn),
+    );
+
+    app.add_systems(RestartRun, reset_campaign_run);
+
+    app.add_systems(
+        Update,
+        (
+            count_campaign_time.run_if(in_state(GameState::Run)),
+            track_campaign_goal,
+        )
+            .chain()
+            .run_if(resource_equals(GameMode::Campaign))
+            .in_set(GameSystems::Update),
+    );
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum CampaignGoal {
+    EatFish { count: u32 },
+    ReachLength { length: usize },
+    Survive { seconds: f32 },
+}
+
+impl CampaignGoal {
+    pub fn label(self) -> String {
+        match self {
+            CampaignGoal::EatFish { count } => format!("Eat {count} fish"),
+            CampaignGoal::ReachLength { length } => format!("Reach length {length}"),
+            CampaignGoal::Survive { seconds } => format!("Survive {seconds:.0} seconds"),
+        }
+    }
+
+    pub fn progress(self, run: &CampaignRun) -> String {
+        match self {
+            CampaignGoal::EatFish { count } => format!("{}/{count}", run.fish),
+            CampaignGoal::ReachLength { length } => format!("{}/{length}", run.length),
+            CampaignGoal::Survive { seconds } => format!("{:.0}/{seconds:.0}", run.seconds),
+        }
+    }
+
+    fn reached(self, run: &CampaignRun) -> bool {
+        match self {
+            CampaignGoal::EatFish { count } => run.fish >= count,
+            CampaignGoal::ReachLength { length } => run.length >= length,
+            CampaignGoal::Survive { seconds } => run.seconds >= seconds,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct CampaignLevel {
+    pub id: String,
+    pub name: String,
+    pub goal: CampaignGoal,
+    pub par_seconds: f32,
+}
+
+impl CampaignLevel {
+    fn stars(&self, run: &CampaignRun) -> u32 {
+        1 + u32::from(run.crashes == 0) + u32::from(run.seconds <= self.par_seconds)
+    }
+}
+
+#[derive(Resource, Asset, TypePath, Clone, Default, Serialize, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct Campaign {
+    pub levels: Vec<CampaignLevel>,
+}
+
+pub fn stars_label(stars: u32) -> String {
+    let stars = stars.min(MAX_STARS) as usize;
+    format!(
+        "[{}{}]",
+        "*".repeat(stars),
+        "-".repeat(MAX_STARS as usize - stars)
+    )
+}
+
+impl Campaign {
+    pub fn level(&self, stage: usize) -> Option<&CampaignLevel> {
+        self.levels.get(stage)
+    }
+}
+
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+pub struct CampaignStage(pub usize);
+
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct CampaignProgress {
+    pub stars: HashMap<String, u32>,
+}
+
+impl CampaignProgress {
+    pub fn stars(&self, level: &CampaignLevel) -> u32 {
+        self.stars.get(&level.id).copied().unwrap_or_default()
+    }
+
+    pub fn unlocked(&self, campaign: &Campaign, stage: usize) -> bool {
+        stage == 0
+            || campaign
+                .level(stage - 1)
+                .is_some_and(|previous| self.stars(previous) > 0)
+    }
+}
+
+#[derive(Resource, Clone, Default, Serialize, Deserialize, Debug)]
+pub struct CampaignRun {
+    pub seconds: f32,
+    pub fish: u32,
+    pub length: usize,
+    pub crashes: u32,
+    pub stars: Option<u32>,
+}
+
+fn reset_campaign_run(mut run: ResMut<CampaignRun>) {
+    *run = CampaignRun::default();
+}
+
+fn count_campaign_time(time: Res<Time>, mut run: ResMut<CampaignRun>) {
+    if run.stars.is_none() {
+        run.seconds += time.delta_secs();
+    }
+}
+
+fn track_campaign_goal(
+    mut run: ResMut<CampaignRun>,
+    mut progress: ResMut<CampaignProgress>,
+    mut food_eaten: EventReader<FoodEaten>,
+    mut length_changed: EventReader<LengthChanged>,
+    mut crashed: EventReader<CatCrashed>,
+    mut goal_reached: EventWriter<GoalReached>,
+    campaign: Res<Campaign>,
+    stage: Res<CampaignStage>,
+    profile: Res<ActiveProfile>,
+) {
+    for event in food_eaten.read() {
+        if event.kind == "fish" {
+            run.fish += 1;
+        }
+    }
+    for event in length_changed.read() {
+        run.length = run.length.max(event.length);
+    }
+    run.crashes += crashed.read().count() as u32;
+
+    let Some(level) = campaign.level(**stage) else {
+        return;
+    };
+    if run.stars.is_some() || !level.goal.reached(&run) {
+        return;
+    }
+
+    let stars = level.stars(&run);
+    run.stars = Some(stars);
+    let best = progress.stars.entry(level.id.clone()).or_default();
+    if stars > *best {
+        *best = stars;
+        storage::save(**profile, CAMPAIGN_FILE, &*progress);
+    }
+    goal_reached.write(GoalReached { stars });
+}
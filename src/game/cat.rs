@@ -1,27 +1,41 @@
 use std::mem;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::{
-    game::{
-        GameSystems, InGame,
-        atlas::{AtlasSprite, SpriteAtlas, atlas_sprite},
-        food::Food,
-        grid::{Cell, CellSize, GRID_CELLS, cell},
-    },
-    screens::Screen,
+use crate::game::{
+    GameSystems, GameTick,
+    atlas::{AtlasSprite, SpriteAtlas, atlas_sprite},
+    events::{CatCrashed, CrashCause, FoodEaten},
+    food::{Food, FoodEffect, FoodRegistry, replenish_food},
+    grid::{Cell, CellSize, cell},
+    interpolation::Motion,
+    level::{Level, Wall},
+    lives::{Invulnerable, Lives},
+    powerup::{ActivePowerUps, PowerUp},
+    ticktimer::SpeedUp,
+    tuning::Tuning,
 };
 
+#[cfg(feature = "dev")]
+use crate::console::{ConsoleArgs, ConsoleExt, ConsoleResult, parse_arg};
+
+const SEGMENTS_LOST_PER_LIFE: usize = 3;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
-        Update,
+        GameTick,
         (move_cat, open_mouth_if_near_food, consume_food, handle_hit)
             .chain()
             .in_set(GameSystems::FixedUpdate),
     );
+
+    #[cfg(feature = "dev")]
+    app.add_console_command("grow", "grow <segments>", grow_command);
 }
 
-#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
 pub enum Direction {
     Left,
     Right,
@@ -29,6 +43,17 @@ pub enum Direction {
     Down,
 }
 
+#[derive(Component, Debug)]
+pub struct Cat;
+
+#[derive(Component, Deref, Debug)]
+#[relationship(relationship_target = Segments)]
+pub struct SegmentOf(Entity);
+
+#[derive(Component, Deref, Debug)]
+#[relationship_target(relationship = SegmentOf, linked_spawn)]
+pub struct Segments(Vec<Entity>);
+
 #[derive(Component, Debug)]
 pub struct CatHead;
 
@@ -107,41 +132,46 @@ fn cat_segment(
     (
         direction,
         cell(position, size),
+        Motion::new(position, direction.to_quat()),
         atlas_sprite(atlas, atlas_index),
     )
 }
 
-fn move_cat(
-    head: Single<(&mut Cell, &mut Direction, &mut Transform, &mut Sprite), With<CatHead>>,
-    mut body: Query<
-        (&mut Cell, &mut Direction, &mut Transform, &mut Sprite),
-        (With<CatBody>, Without<CatHead>),
-    >,
-    tail: Single<
-        (&mut Cell, &mut Direction, &mut Transform, &mut Sprite),
-        (With<CatTail>, Without<CatHead>, Without<CatBody>),
-    >,
+pub(super) fn move_cat(
+    cat: Single<&Segments, With<Cat>>,
+    mut segments: Query<(&mut Cell, &mut Direction, &mut Transform, &mut Sprite)>,
     size: Res<CellSize>,
+    level: Res<Level>,
 ) {
-    let (mut cell, mut dir, mut transform, mut sprite) = head.into_inner();
+    let [head, body @ .., tail] = cat.as_slice() else {
+        return;
+    };
+
+    let Ok((mut cell, mut dir, mut transform, mut sprite)) = segments.get_mut(*head) else {
+        return;
+    };
 
     let mut prev_dir = *dir;
     let mut next_dir = prev_dir;
     let mut prev_cell = **cell;
 
     *dir = next_dir;
-    **cell = (**cell + next_dir.to_vec()).rem_euclid(GRID_CELLS.as_vec2());
+    **cell = level.wrap(**cell + next_dir.to_vec());
+    if let Some(exit) = level.portal_exit(**cell) {
+        **cell = exit;
+    }
     transform.translation = Vec3::from((**cell * **size, transform.translation.z));
     transform.rotation = dir.to_quat();
     if let Some(atlas) = sprite.texture_atlas.as_mut() {
-        atlas.index = if atlas.index == AtlasSprite::Head1.into() {
+        atlas.index = if atlas.index == usize::from(AtlasSprite::Head1) {
             AtlasSprite::Head2.into()
         } else {
             AtlasSprite::Head1.into()
         };
     }
 
-    for (mut cell, mut dir, mut transform, mut sprite) in &mut body {
+    let mut body = segments.iter_many_mut(body);
+    while let Some((mut cell, mut dir, mut transform, mut sprite)) = body.fetch_next() {
         mem::swap(&mut prev_cell, &mut **cell);
         let cross = dir.to_vec().perp_dot(next_dir.to_vec());
         sprite.texture_atlas.as_mut().unwrap().index = if cross == 0.0 {
@@ -159,13 +189,15 @@ fn move_cat(
         }
     }
 
-    let (mut cell, mut dir, mut transform, mut sprite) = tail.into_inner();
+    let Ok((mut cell, mut dir, mut transform, mut sprite)) = segments.get_mut(*tail) else {
+        return;
+    };
     **cell = prev_cell;
     *dir = next_dir;
     transform.translation = Vec3::from((**cell * **size, transform.translation.z));
     transform.rotation = dir.to_quat();
     if let Some(atlas) = sprite.texture_atlas.as_mut() {
-        atlas.index = if atlas.index == AtlasSprite::Tail1.into() {
+        atlas.index = if atlas.index == usize::from(AtlasSprite::Tail1) {
             AtlasSprite::Tail2.into()
         } else {
             AtlasSprite::Tail1.into()
@@ -173,93 +205,209 @@ fn move_cat(
     }
 }
 
-fn open_mouth_if_near_food(
+pub(super) fn open_mouth_if_near_food(
     head: Single<(&Cell, &Direction, &mut Sprite), With<CatHead>>,
-    food: Single<&Cell, With<Food>>,
+    food: Query<&Cell, With<Food>>,
+    level: Res<Level>,
 ) {
     let (head_cell, head_dir, mut head_sprite) = head.into_inner();
 
     let dir_vec = head_dir.to_vec();
-    if [dir_vec, dir_vec.perp(), -dir_vec.perp()]
-        .map(|dir| (**head_cell + dir).rem_euclid(GRID_CELLS.as_vec2()))
-        .contains(*food)
+    let adjacent =
+        [dir_vec, dir_vec.perp(), -dir_vec.perp()].map(|dir| Cell(level.wrap(**head_cell + dir)));
+    if food.iter().any(|cell| adjacent.contains(cell))
+        && let Some(atlas) = head_sprite.texture_atlas.as_mut()
     {
-        if let Some(atlas) = head_sprite.texture_atlas.as_mut() {
-            atlas.index = if atlas.index == AtlasSprite::Head1.into() {
-                AtlasSprite::Head3.into()
-            } else {
-                AtlasSprite::Head4.into()
-            };
-        }
+        atlas.index = if atlas.index == usize::from(AtlasSprite::Head1) {
+            AtlasSprite::Head3.into()
+        } else {
+            AtlasSprite::Head4.into()
+        };
     }
 }
 
-fn handle_hit(
+pub(super) fn handle_hit(
     mut commands: Commands,
-    head: Single<(&mut Cell, &Direction, &mut Transform, &mut Sprite), With<CatHead>>,
-    rest: Query<(Entity, &Cell), (Or<(With<CatBody>, With<CatTail>)>, Without<CatHead>)>,
+    head: Single<
+        (
+            Entity,
+            &mut Cell,
+            &Direction,
+            &mut Transform,
+            &mut Sprite,
+            Has<Invulnerable>,
+        ),
+        With<CatHead>,
+    >,
+    cat: Single<&Segments, With<Cat>>,
+    rest: Query<&Cell, (Or<(With<CatBody>, With<CatTail>)>, Without<CatHead>)>,
+    walls: Query<&Cell, (With<Wall>, Without<CatHead>)>,
     size: Res<CellSize>,
-    mut next_screen: ResMut<NextState<Screen>>,
+    level: Res<Level>,
+    active_power_ups: Res<ActivePowerUps>,
+    mut lives: ResMut<Lives>,
+    mut crashed: EventWriter<CatCrashed>,
 ) {
-    let (mut head_cell, head_dir, mut head_transform, mut head_sprite) = head.into_inner();
-    if rest.iter().any(|(_, cell)| *cell == *head_cell) {
-        commands.entity(rest.iter().next().unwrap().0).despawn();
-        **head_cell = (**head_cell + (-head_dir.to_vec())).rem_euclid(GRID_CELLS.as_vec2());
-        head_transform.translation =
-            Vec3::from((**head_cell * **size, head_transform.translation.z));
-        if let Some(atlas) = head_sprite.texture_atlas.as_mut() {
-            atlas.index = AtlasSprite::Head5.into();
+    let (head_entity, mut head_cell, head_dir, mut head_transform, mut head_sprite, invulnerable) =
+        head.into_inner();
+    if invulnerable || active_power_ups.contains(PowerUp::Ghost) {
+        return;
+    }
+
+    let cause = if walls.iter().any(|cell| *cell == *head_cell) {
+        CrashCause::Wall
+    } else if rest.iter().any(|cell| *cell == *head_cell) {
+        CrashCause::Body
+    } else {
+        return;
+    };
+
+    if level.rules.lives {
+        **lives = lives.saturating_sub(1);
+        if **lives > 0 {
+            crashed.write(CatCrashed {
+                cause,
+                cell: **head_cell,
+                fatal: false,
+            });
+            commands.entity(head_entity).insert(Invulnerable::default());
+            commands.run_system_cached_with(shrink_cat, SEGMENTS_LOST_PER_LIFE);
+            return;
         }
-        next_screen.set(Screen::Over)
+    }
+
+    crashed.write(CatCrashed {
+        cause,
+        cell: **head_cell,
+        fatal: true,
+    });
+
+    commands.entity(cat[1]).despawn();
+    **head_cell = level.wrap(**head_cell - head_dir.to_vec());
+    head_transform.translation = Vec3::from((**head_cell * **size, head_transform.translation.z));
+    if let Some(atlas) = head_sprite.texture_atlas.as_mut() {
+        atlas.index = AtlasSprite::Head5.into();
+    }
+}
+
+pub fn shrink_cat(
+    In(amount): In<usize>,
+    mut commands: Commands,
+    cat: Single<&Segments, With<Cat>>,
+    mut segments: Query<(&mut Cell, &mut Direction, &mut Transform, &mut Motion)>,
+) {
+    let [_, body @ .., tail] = cat.as_slice() else {
+        return;
+    };
+
+    let amount = amount.min(body.len().saturating_sub(1));
+    if amount == 0 {
+        return;
+    }
+
+    let removed = &body[body.len() - amount..];
+    let Ok((cell, dir, transform, motion)) = segments
+        .get(removed[0])
+        .map(|(cell, dir, transform, motion)| (*cell, *dir, *transform, *motion))
+    else {
+        return;
+    };
+
+    let Ok((mut tail_cell, mut tail_dir, mut tail_transform, mut tail_motion)) =
+        segments.get_mut(*tail)
+    else {
+        return;
+    };
+    *tail_cell = cell;
+    *tail_dir = dir;
+    *tail_motion = motion;
+    tail_transform.translation = transform.translation;
+    tail_transform.rotation = dir.to_quat();
+
+    for entity in removed {
+        commands.entity(*entity).despawn();
     }
 }
 
-fn consume_food(
+pub(super) fn consume_food(
     mut commands: Commands,
     head: Single<&Cell, With<CatHead>>,
-    body: Query<&Cell, With<CatBody>>,
-    tail: Single<(&Cell, &Direction, &Transform, &Sprite), With<CatTail>>,
-    food: Single<
-        (&mut Cell, &mut Transform),
-        (
-            With<Food>,
-            Without<CatHead>,
-            Without<CatBody>,
-            Without<CatTail>,
-        ),
-    >,
-    size: Res<CellSize>,
+    food: Query<(Entity, &Cell, &Food)>,
+    registry: Res<FoodRegistry>,
+    tuning: Res<Tuning>,
+    mut active_power_ups: ResMut<ActivePowerUps>,
+    mut lives: ResMut<Lives>,
+    mut food_eaten: EventWriter<FoodEaten>,
 ) {
-    let (mut food_cell, mut food_transform) = food.into_inner();
-    let (tail_cell, tail_dir, tail_transform, tail_sprite) = tail.into_inner();
-
-    // TODO: create a list of empty cells instead and if the list is empty switch to a win state or someting
-    if **head == *food_cell {
-        let mut new_pos = Vec2::new(
-            rand::random_range(0..GRID_CELLS.x) as f32,
-            rand::random_range(0..GRID_CELLS.y) as f32,
-        );
-
-        while ***head == new_pos
-            || body.iter().any(|cell| **cell == new_pos)
-            || **tail_cell == new_pos
-        {
-            new_pos = Vec2::new(
-                rand::random_range(0..GRID_CELLS.x) as f32,
-                rand::random_range(0..GRID_CELLS.y) as f32,
-            );
+    let Some((food_entity, _, food)) = food.iter().find(|(_, cell, _)| *cell == *head) else {
+        return;
+    };
+
+    commands.entity(food_entity).despawn();
+    commands.run_system_cached(replenish_food);
+    food_eaten.write(FoodEaten {
+        cell: ***head,
+        kind: food.kind.clone(),
+    });
+
+    let Some(definition) = registry.get(&food.kind) else {
+        return;
+    };
+
+    commands.run_system_cached_with(grow_cat, definition.growth);
+
+    match definition.effect {
+        Some(FoodEffect::Shrink(amount)) => {
+            commands.run_system_cached_with(shrink_cat, amount);
         }
+        Some(FoodEffect::SpeedUp { factor, ticks }) => {
+            commands.insert_resource(SpeedUp { factor, ticks });
+        }
+        Some(FoodEffect::ExtraLife) => lives.gain(),
+        Some(FoodEffect::PowerUp(power_up)) => {
+            active_power_ups.activate(power_up, tuning.power_up_rule(power_up));
+        }
+        None => {}
+    }
+}
+
+pub fn grow_cat(
+    In(amount): In<usize>,
+    mut commands: Commands,
+    cat: Single<(Entity, &Segments), With<Cat>>,
+    tail: Single<(&Cell, &Direction, &Transform, &Motion, &Sprite), With<CatTail>>,
+) {
+    let (cat, segments) = cat.into_inner();
+    let (tail_cell, tail_dir, tail_transform, tail_motion, tail_sprite) = tail.into_inner();
+    let grown = (0..amount)
+        .map(|_| {
+            commands
+                .spawn((
+                    CatBody,
+                    *tail_cell,
+                    *tail_dir,
+                    *tail_transform,
+                    *tail_motion,
+                    tail_sprite.clone(),
+                ))
+                .id()
+        })
+        .collect::<Vec<_>>();
+    commands
+        .entity(cat)
+        .insert_related::<SegmentOf>(segments.len() - 1, &grown);
+}
 
-        **food_cell = new_pos;
-        food_transform.translation = Vec3::from((new_pos * **size, food_transform.translation.z));
-
-        commands.spawn((
-            StateScoped(InGame::True),
-            CatBody,
-            *tail_cell,
-            *tail_dir,
-            *tail_transform,
-            tail_sprite.clone(),
-        ));
+#[cfg(feature = "dev")]
+fn grow_command(
+    In(args): In<ConsoleArgs>,
+    mut commands: Commands,
+    cat: Query<(), With<Cat>>,
+) -> ConsoleResult {
+    let amount = parse_arg::<usize>(&args, 0, "segments")?;
+    if cat.is_empty() {
+        return Err("no cat to grow".into());
     }
+    commands.run_system_cached_with(grow_cat, amount);
+    Ok(format!("grew the cat by {amount}"))
 }
@@ -1,25 +1,37 @@
 use std::collections::VecDeque;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::game::{
-    GameSystems,
-    cat::{CatBody, CatHead, CatTail, Direction},
-    grid::{Cell, GRID_CELLS},
+use crate::{
+    game::{
+        GameState, GameSystems, GameTick, RestartRun,
+        cat::{CatBody, CatHead, CatTail, Direction},
+        events::DirectionChanged,
+        grid::Cell,
+        level::Level,
+    },
+    settings::Settings,
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<InputBuffer>();
+
     app.add_systems(
-        Update,
-        (
-            control_cat.in_set(GameSystems::Input),
-            update_cat_head_dir.in_set(GameSystems::PreFixedUpdate),
-        ),
+        OnExit(GameState::None),
+        reset_input_buffer.in_set(GameSystems::Spawn),
+    );
+
+    app.add_systems(RestartRun, reset_input_buffer);
+
+    app.add_systems(Update, control_cat.in_set(GameSystems::Input));
+    app.add_systems(
+        GameTick,
+        update_cat_head_dir.in_set(GameSystems::PreFixedUpdate),
     );
 }
 
-#[derive(Resource, Default)]
+#[derive(Resource, Clone, Default, Serialize, Deserialize, Debug)]
 pub struct InputBuffer {
     deque: VecDeque<Direction>,
 }
@@ -35,16 +47,34 @@ impl InputBuffer {
     pub fn pop(&mut self) -> Option<Direction> {
         self.deque.pop_front()
     }
+
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    #[cfg(feature = "dev")]
+    pub fn iter(&self) -> impl Iterator<Item = &Direction> {
+        self.deque.iter()
+    }
+}
+
+fn reset_input_buffer(mut input_buffer: ResMut<InputBuffer>) {
+    *input_buffer = InputBuffer::default();
 }
 
-fn control_cat(keyboard_input: Res<ButtonInput<KeyCode>>, mut input_buffer: ResMut<InputBuffer>) {
-    if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+fn control_cat(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut input_buffer: ResMut<InputBuffer>,
+    settings: Res<Settings>,
+) {
+    let bindings = settings.key_bindings;
+    if keyboard_input.just_pressed(bindings.left) {
         input_buffer.push(Direction::Left);
-    } else if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+    } else if keyboard_input.just_pressed(bindings.right) {
         input_buffer.push(Direction::Right);
-    } else if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+    } else if keyboard_input.just_pressed(bindings.up) {
         input_buffer.push(Direction::Up);
-    } else if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+    } else if keyboard_input.just_pressed(bindings.down) {
         input_buffer.push(Direction::Down);
     }
 }
@@ -53,6 +83,8 @@ fn update_cat_head_dir(
     head: Single<(&Cell, &mut Direction), With<CatHead>>,
     rest: Query<(Entity, &Cell), (Or<(With<CatBody>, With<CatTail>)>, Without<CatHead>)>,
     mut input_buffer: ResMut<InputBuffer>,
+    mut direction_changed: EventWriter<DirectionChanged>,
+    level: Res<Level>,
 ) {
     let (cell, mut dir) = head.into_inner();
     while let Some(new_dir) = input_buffer.pop() {
@@ -60,11 +92,16 @@ fn update_cat_head_dir(
             continue;
         }
 
-        let next_cell = (**cell + new_dir.to_vec()).rem_euclid(GRID_CELLS.as_vec2());
+        let next_cell = level.wrap(**cell + new_dir.to_vec());
         if rest.iter().any(|(_, cell)| **cell == next_cell) {
             continue;
         }
 
+        direction_changed.write(DirectionChanged {
+            cell: **cell,
+            from: *dir,
+            to: new_dir,
+        });
         *dir = new_dir;
         break;
     }
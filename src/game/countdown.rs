@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+use crate::{game::GameState, settings::Settings};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Countdown), start_countdown);
+    app.add_systems(
+        Update,
+        advance_countdown.run_if(in_state(GameState::Countdown)),
+    );
+}
+
+#[derive(Resource, Deref, DerefMut, Debug)]
+struct Countdown(Timer);
+
+#[derive(Component, Debug)]
+struct CountdownText;
+
+pub fn start_state(settings: &Settings) -> GameState {
+    if settings.countdown > 0 {
+        GameState::Countdown
+    } else {
+        GameState::Run
+    }
+}
+
+fn start_countdown(mut commands: Commands, settings: Res<Settings>) {
+    commands.insert_resource(Countdown(Timer::from_seconds(
+        settings.countdown.max(1) as f32,
+        TimerMode::Once,
+    )));
+
+    commands.spawn((
+        StateScoped(GameState::Countdown),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![(
+            CountdownText,
+            Text::new(settings.countdown.to_string()),
+            TextFont::from_font_size(160.0),
+            TextColor(Color::srgb_u8(0xe0, 0xe0, 0xe0)),
+        )],
+    ));
+}
+
+fn advance_countdown(
+    mut countdown: ResMut<Countdown>,
+    mut text: Single<&mut Text, With<CountdownText>>,
+    mut next_gameplay: ResMut<NextState<GameState>>,
+    time: Res<Time>,
+) {
+    if countdown.tick(time.delta()).finished() {
+        next_gameplay.set(GameState::Run);
+        return;
+    }
+
+    text.0 = (countdown.remaining_secs().ceil() as u32).to_string();
+}
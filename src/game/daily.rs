@@ -0,0 +1,218 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        GameMode, GameState, GameSystems,
+        cat::{Cat, Direction, Segments},
+        level::{Level, Rules},
+        savegame::RunSaved,
+        score::Score,
+        ticktimer::Tick,
+    },
+    profiles::{ActiveProfile, load_profile_data},
+    settings::Difficulty,
+    storage,
+};
+
+const DAILY_FILE: &str = "daily.json";
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<DailyHistory>();
+    app.add_systems(
+        PreUpdate,
+        load_profile_data::<DailyHistory>(DAILY_FILE).run_if(resource_changed::<ActiveProfile>),
+    );
+    app.insert_resource(DailyChallenge::today());
+    app.init_resource::<DailyAttempt>();
+
+    app.add_systems(
+        OnExit(GameState::None),
+        start_daily_attempt
+            .run_if(resource_equals(GameMode::Daily))
+            .before(GameSystems::Level),
+    );
+
+    app.add_systems(OnEnter(GameState::Over), record_daily_result);
+    app.add_systems(
+        OnEnter(GameState::None),
+        record_daily_result.run_if(not(resource_exists::<RunSaved>)),
+    );
+}
+
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct DailyChallenge {
+    pub day: i64,
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        let seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        Self {
+            day: (seconds / SECONDS_PER_DAY) as i64,
+        }
+    }
+
+    pub fn date(self) -> String {
+        let (year, month, day) = civil_from_days(self.day);
+        format!("{year:04}-{month:02}-{day:02}")
+    }
+
+    pub fn seed(self) -> u64 {
+        let (year, month, day) = civil_from_days(self.day);
+        (year as u64 * 10000 + month as u64 * 100 + day as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+    }
+
+    pub fn level(self) -> Level {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed());
+
+        let size = IVec2::new(rng.random_range(16..=28), rng.random_range(9..=14));
+        let start = size / 2;
+
+        let mut walls = Vec::new();
+        for _ in 0..rng.random_range(2..=6) {
+            let horizontal = rng.random_bool(0.5);
+            let length = rng.random_range(3..=6);
+            let origin = IVec2::new(rng.random_range(0..size.x), rng.random_range(0..size.y));
+            for offset in 0..length {
+                let wall = if horizontal {
+                    IVec2::new((origin.x + offset).rem_euclid(size.x), origin.y)
+                } else {
+                    IVec2::new(origin.x, (origin.y + offset).rem_euclid(size.y))
+                };
+                if wall.y != start.y && !walls.contains(&wall) {
+                    walls.push(wall);
+                }
+            }
+        }
+
+        let difficulty = Difficulty::ALL[rng.random_range(0..Difficulty::ALL.len())];
+        Level {
+            size,
+            walls,
+            portals: Vec::new(),
+            tiles: Vec::new(),
+            script: None,
+            start,
+            direction: Direction::Right,
+            rules: Rules {
+                lives: rng.random_bool(0.5),
+                food_count: rng.random_range(1..=3),
+                difficulty,
+            },
+        }
+    }
+}
+
+fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = (year_of_era + era * 400 + i64::from(month <= 2)) as i32;
+    (year, month, day)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct DailyResult {
+    pub day: i64,
+    pub score: u32,
+    pub length: usize,
+    pub ticks: u64,
+}
+
+impl DailyResult {
+    pub fn code(&self) -> String {
+        let date = DailyChallenge { day: self.day }.date().replace('-', "");
+        let checksum = format!("{date}{}{}{}", self.score, self.length, self.ticks)
+            .bytes()
+            .fold(0x811c_9dc5_u32, |hash, byte| {
+                (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+            });
+        format!(
+            "LC{date}-S{}-L{}-T{}-{:04X}",
+            self.score,
+            self.length,
+            self.ticks,
+            checksum & 0xffff
+        )
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct DailyHistory {
+    pub results: Vec<DailyResult>,
+}
+
+impl DailyHistory {
+    pub fn result(&self, challenge: DailyChallenge) -> Option<&DailyResult> {
+        self.results
+            .iter()
+            .find(|result| result.day == challenge.day)
+    }
+
+    pub fn streak(&self, today: DailyChallenge) -> u32 {
+        let mut day = if self.result(today).is_some() {
+            today.day
+        } else {
+            today.day - 1
+        };
+
+        let mut streak = 0;
+        while self.results.iter().any(|result| result.day == day) {
+            streak += 1;
+            day -= 1;
+        }
+        streak
+    }
+}
+
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+pub struct DailyAttempt(Option<DailyChallenge>);
+
+fn start_daily_attempt(
+    mut challenge: ResMut<DailyChallenge>,
+    mut attempt: ResMut<DailyAttempt>,
+    history: Res<DailyHistory>,
+) {
+    *challenge = DailyChallenge::today();
+    attempt.0 = history.result(*challenge).is_none().then_some(*challenge);
+}
+
+fn record_daily_result(
+    mut attempt: ResMut<DailyAttempt>,
+    mut history: ResMut<DailyHistory>,
+    score: Res<Score>,
+    tick: Res<Tick>,
+    cat: Query<&Segments, With<Cat>>,
+    profile: Res<ActiveProfile>,
+) {
+    let Some(challenge) = attempt.0.take() else {
+        return;
+    };
+
+    history.results.push(DailyResult {
+        day: challenge.day,
+        score: **score,
+        length: cat.single().map_or(0, |segments| segments.len()),
+        ticks: **tick,
+    });
+    storage::save(**profile, DAILY_FILE, &*history);
+}
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    game::{
+        GameState, GameSystems, GameTick, InGame,
+        atlas::SpriteAtlas,
+        cat::{Cat, SegmentOf, Segments, grow_cat},
+        control::InputBuffer,
+        food::{Food, FoodRegistry, food},
+        grid::{Cell, CellSize},
+        level::{Level, Portal, TileKind, Wall},
+        ticktimer::{Tick, TickTimer},
+    },
+    menus::Menu,
+    screens::Screen,
+};
+
+const TOGGLE_KEY: KeyCode = KeyCode::F3;
+const FREEZE_KEY: KeyCode = KeyCode::F4;
+const STEP_KEY: KeyCode = KeyCode::F5;
+const PLACED_FOOD: &str = "fish";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<DebugOverlay>();
+
+    app.configure_sets(
+        Update,
+        GameSystems::TickTimers.run_if(|overlay: Res<DebugOverlay>| !overlay.frozen),
+    );
+
+    app.add_systems(
+        Update,
+        (
+            toggle_overlay.run_if(input_just_pressed(TOGGLE_KEY)),
+            toggle_freeze.run_if(input_just_pressed(FREEZE_KEY)),
+            step_tick
+                .after(GameSystems::Ticks)
+                .before(GameSystems::Update)
+                .run_if(
+                    in_state(GameState::Run)
+                        .and(|overlay: Res<DebugOverlay>| overlay.frozen)
+                        .and(input_just_pressed(STEP_KEY)),
+                ),
+            (
+                spawn_info_panel.run_if(not(any_with_component::<DebugInfo>)),
+                update_cell_labels,
+                update_info_text,
+                edit_on_click.run_if(in_state(GameState::Run)),
+            )
+                .chain()
+                .run_if(in_state(InGame::True).and(|overlay: Res<DebugOverlay>| overlay.visible)),
+            despawn_overlay.run_if(resource_changed::<DebugOverlay>),
+        ),
+    );
+}
+
+#[derive(Resource, Default, Debug)]
+struct DebugOverlay {
+    visible: bool,
+    frozen: bool,
+}
+
+#[derive(Component, Debug)]
+struct DebugInfo;
+
+#[derive(Component, Debug)]
+struct DebugCellLabel(IVec2);
+
+fn toggle_overlay(mut overlay: ResMut<DebugOverlay>) {
+    overlay.visible = !overlay.visible;
+}
+
+fn toggle_freeze(mut overlay: ResMut<DebugOverlay>) {
+    overlay.frozen = !overlay.frozen;
+}
+
+fn step_tick(world: &mut World) {
+    world.run_schedule(GameTick);
+}
+
+fn despawn_overlay(
+    mut commands: Commands,
+    overlay: Res<DebugOverlay>,
+    entities: Query<Entity, Or<(With<DebugInfo>, With<DebugCellLabel>)>>,
+) {
+    if overlay.visible {
+        return;
+    }
+    for entity in &entities {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn_info_panel(mut commands: Commands) {
+    commands.spawn((
+        DebugInfo,
+        StateScoped(InGame::True),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(20.0),
+            bottom: Val::Px(20.0),
+            padding: UiRect::all(Val::Px(10.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        GlobalZIndex(1),
+        Text::default(),
+        TextFont::from_font_size(16.0),
+        TextColor(Color::srgb_u8(0x60, 0xff, 0x60)),
+    ));
+}
+
+fn occupancy(
+    level: &Level,
+    segments: Option<&Segments>,
+    cells: &Query<&Cell>,
+    walls: &Query<&Cell, With<Wall>>,
+    portals: &Query<&Cell, With<Portal>>,
+    foods: &Query<&Cell, With<Food>>,
+) -> HashMap<IVec2, Vec<String>> {
+    let mut occupancy = HashMap::<IVec2, Vec<String>>::new();
+    let mut mark = |cell: Vec2, label: String| {
+        occupancy.entry(cell.as_ivec2()).or_default().push(label);
+    };
+
+    for cell in walls {
+        mark(**cell, "#".into());
+    }
+    for cell in portals {
+        mark(**cell, "O".into());
+    }
+    for tile in &level.tiles {
+        let label = match tile.kind {
+            TileKind::Boost => "B",
+            TileKind::Slow => "S",
+        };
+        mark(tile.cell.as_vec2(), label.into());
+    }
+    for cell in foods {
+        mark(**cell, "F".into());
+    }
+    if let Some(segments) = segments {
+        for (index, cell) in cells.iter_many(segments.iter()).enumerate() {
+            mark(**cell, index.to_string());
+        }
+    }
+    occupancy
+}
+
+fn update_cell_labels(
+    mut commands: Commands,
+    mut labels: Query<(
+        Entity,
+        &DebugCellLabel,
+        &mut Text2d,
+        &mut Transform,
+        &mut TextFont,
+    )>,
+    cat: Query<&Segments, With<Cat>>,
+    cells: Query<&Cell>,
+    walls: Query<&Cell, With<Wall>>,
+    portals: Query<&Cell, With<Portal>>,
+    foods: Query<&Cell, With<Food>>,
+    level: Res<Level>,
+    size: Res<CellSize>,
+) {
+    let cell_count = (level.size.x * level.size.y) as usize;
+    if labels.iter().len() != cell_count {
+        for (entity, ..) in &labels {
+            commands.entity(entity).despawn();
+        }
+        for y in 0..level.size.y {
+            for x in 0..level.size.x {
+                commands.spawn((
+                    DebugCellLabel(IVec2::new(x, y)),
+                    StateScoped(InGame::True),
+                    Text2d::default(),
+                    TextFont::default(),
+                    TextColor(Color::srgba(1.0, 1.0, 1.0, 0.6)),
+                    Transform::default(),
+                ));
+            }
+        }
+        return;
+    }
+
+    let occupancy = occupancy(&level, cat.single().ok(), &cells, &walls, &portals, &foods);
+    for (_, label, mut text, mut transform, mut font) in &mut labels {
+        let occupants = occupancy
+            .get(&label.0)
+            .map(|occupants| occupants.join("/"))
+            .unwrap_or_default();
+        text.0 = format!("{},{}\n{occupants}", label.0.x, label.0.y);
+        font.font_size = (**size / 4.0).max(6.0);
+        transform.translation = Vec3::from((label.0.as_vec2() * **size, 10.0));
+    }
+}
+
+fn update_info_text(
+    mut info: Single<&mut Text, With<DebugInfo>>,
+    input_buffer: Res<InputBuffer>,
+    (tick, timer, overlay): (Res<Tick>, Res<TickTimer>, Res<DebugOverlay>),
+    (screen, menu, game_state, in_game): (
+        Res<State<Screen>>,
+        Res<State<Menu>>,
+        Res<State<GameState>>,
+        Res<State<InGame>>,
+    ),
+) {
+    let inputs = input_buffer
+        .iter()
+        .map(|direction| format!("{direction:?}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    info.0 = format!(
+        "Screen: {:?}  Menu: {:?}\nGameState: {:?}  InGame: {:?}\nTick: {}  Interval: {:.3}s  Phase: {:.2}\nInput: [{inputs}]\nFrozen: {}\n\n{TOGGLE_KEY:?}: overlay  {FREEZE_KEY:?}: freeze  {STEP_KEY:?}: step\nLeft click: place food  Right click: grow",
+        screen.get(),
+        menu.get(),
+        game_state.get(),
+        in_game.get(),
+        **tick,
+        timer.duration().as_secs_f32(),
+        timer.fraction(),
+        if overlay.frozen { "yes" } else { "no" },
+    );
+}
+
+fn edit_on_click(
+    mut commands: Commands,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    occupied: Query<&Cell, Or<(With<SegmentOf>, With<Food>, With<Wall>, With<Portal>)>>,
+    registry: Res<FoodRegistry>,
+    atlas: Res<SpriteAtlas>,
+    level: Res<Level>,
+    size: Res<CellSize>,
+) {
+    if mouse_input.just_pressed(MouseButton::Right) {
+        commands.run_system_cached_with(grow_cat, 1);
+    }
+    if !mouse_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let (camera, camera_transform) = *camera;
+    let Some(position) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+
+    let cell = (position / **size).round();
+    if cell.cmplt(Vec2::ZERO).any()
+        || cell.cmpge(level.cells()).any()
+        || occupied.iter().any(|occupied| **occupied == cell)
+    {
+        return;
+    }
+    let Some(definition) = registry.get(PLACED_FOOD) else {
+        return;
+    };
+    commands.spawn((
+        StateScoped(InGame::True),
+        food(cell, **size, &atlas, definition),
+    ));
+}
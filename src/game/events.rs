@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    GameSystems, GameTick,
+    cat::{Cat, Direction, Segments},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<TickAdvanced>();
+    app.add_event::<FoodEaten>();
+    app.add_event::<CatCrashed>();
+    app.add_event::<DirectionChanged>();
+    app.add_event::<LengthChanged>();
+    app.add_event::<BoardCleared>();
+    app.add_event::<GoalReached>();
+    app.add_event::<RunEnded>();
+    app.add_event::<LevelChanged>();
+
+    app.add_systems(
+        GameTick,
+        detect_length_change.in_set(GameSystems::PostFixedUpdate),
+    );
+    app.add_systems(Update, log_events.in_set(GameSystems::Update));
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct TickAdvanced {
+    pub tick: u64,
+}
+
+#[derive(Event, Clone, Debug)]
+pub struct FoodEaten {
+    pub cell: Vec2,
+    pub kind: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashCause {
+    Body,
+    Wall,
+}
+
+impl CrashCause {
+    pub fn label(self) -> &'static str {
+        match self {
+            CrashCause::Body => "Own tail",
+            CrashCause::Wall => "Wall",
+        }
+    }
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CatCrashed {
+    pub cause: CrashCause,
+    pub cell: Vec2,
+    pub fatal: bool,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct DirectionChanged {
+    pub cell: Vec2,
+    pub from: Direction,
+    pub to: Direction,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct LengthChanged {
+    pub length: usize,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct BoardCleared;
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct GoalReached {
+    pub stars: u32,
+}
+
+#[derive(Event, Clone, Copy, Debug)]
+pub struct RunEnded;
+
+#[derive(Event, Clone, Debug)]
+pub struct LevelChanged {
+    pub id: String,
+}
+
+pub fn detect_length_change(
+    cat: Query<&Segments, (With<Cat>, Changed<Segments>)>,
+    mut length_changed: EventWriter<LengthChanged>,
+) {
+    for segments in &cat {
+        length_changed.write(LengthChanged {
+            length: segments.len(),
+        });
+    }
+}
+
+fn log_events(
+    mut tick_advanced: EventReader<TickAdvanced>,
+    mut food_eaten: EventReader<FoodEaten>,
+    mut crashed: EventReader<CatCrashed>,
+    mut direction_changed: EventReader<DirectionChanged>,
+    mut length_changed: EventReader<LengthChanged>,
+    mut board_cleared: EventReader<BoardCleared>,
+    mut goal_reached: EventReader<GoalReached>,
+    mut level_changed: EventReader<LevelChanged>,
+) {
+    for event in tick_advanced.read() {
+        trace!("tick {}", event.tick);
+    }
+    for event in food_eaten.read() {
+        debug!("ate {} at {}", event.kind, event.cell);
+    }
+    for event in crashed.read() {
+        debug!(
+            "crashed into {:?} at {} (fatal: {})",
+            event.cause, event.cell, event.fatal
+        );
+    }
+    for event in direction_changed.read() {
+        debug!(
+            "turned from {:?} to {:?} at {}",
+            event.from, event.to, event.cell
+        );
+    }
+    for event in length_changed.read() {
+        debug!("length is now {}", event.length);
+    }
+    for _ in board_cleared.read() {
+        debug!("board cleared");
+    }
+    for event in goal_reached.read() {
+        debug!("goal reached with {} stars", event.stars);
+    }
+    for event in level_changed.read() {
+        debug!("level {} changed", event.id);
+    }
+}
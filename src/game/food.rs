@@ -1,13 +1,245 @@
 use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "dev")]
+use crate::console::{ConsoleArgs, ConsoleExt, ConsoleResult, parse_arg};
 use crate::game::{
-    atlas::{SpriteAtlas, atlas_sprite},
-    grid::cell,
+    GameSystems, GameTick, InGame,
+    atlas::{AtlasSprite, SpriteAtlas, tinted_atlas_sprite},
+    cat::{CatBody, CatHead, CatTail, consume_food},
+    events::BoardCleared,
+    grid::{Cell, CellSize, cell, random_free_cell},
+    level::{Level, Portal, Wall},
+    powerup::PowerUp,
+    rng::GameRng,
+    tuning::Tuning,
 };
 
-#[derive(Component)]
-pub struct Food;
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<FoodRegistry>();
+    app.add_systems(
+        GameTick,
+        expire_food
+            .after(consume_food)
+            .in_set(GameSystems::FixedUpdate),
+    );
 
-pub fn food(position: Vec2, size: f32, atlas: &SpriteAtlas, atlas_index: usize) -> impl Bundle {
-    (Food, cell(position, size), atlas_sprite(atlas, atlas_index))
+    #[cfg(feature = "dev")]
+    app.add_console_command("food", "food <x> <y> [kind]", food_command);
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum FoodEffect {
+    Shrink(usize),
+    SpeedUp { factor: f32, ticks: u32 },
+    ExtraLife,
+    PowerUp(PowerUp),
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct FoodDefinition {
+    pub name: String,
+    pub sprite: AtlasSprite,
+    pub color: Color,
+    pub weight: u32,
+    pub points: u32,
+    pub growth: usize,
+    pub lifetime: Option<u32>,
+    pub effect: Option<FoodEffect>,
+}
+
+#[derive(Resource, Debug)]
+pub struct FoodRegistry {
+    foods: Vec<FoodDefinition>,
+}
+
+impl FoodRegistry {
+    pub fn new(foods: Vec<FoodDefinition>) -> Self {
+        Self { foods }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&FoodDefinition> {
+        self.foods.iter().find(|food| food.name == name)
+    }
+
+    pub fn choose(
+        &self,
+        rng: &mut impl Rng,
+        tuning: &Tuning,
+        filter: impl Fn(&FoodDefinition) -> bool,
+    ) -> Option<&FoodDefinition> {
+        let candidates = self
+            .foods
+            .iter()
+            .map(|food| (food, tuning.food_weight(food)))
+            .filter(|(food, weight)| *weight > 0 && filter(food))
+            .collect::<Vec<_>>();
+
+        let total = candidates.iter().map(|(_, weight)| weight).sum::<u32>();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rng.random_range(0..total);
+        candidates
+            .into_iter()
+            .find(|(_, weight)| {
+                if roll < *weight {
+                    true
+                } else {
+                    roll -= weight;
+                    false
+                }
+            })
+            .map(|(food, _)| food)
+    }
+}
+
+impl Default for FoodRegistry {
+    fn default() -> Self {
+        Self {
+            foods: vec![FoodDefinition {
+                name: "fish".into(),
+                sprite: AtlasSprite::Fish,
+                color: Color::WHITE,
+                weight: 1,
+                points: 1,
+                growth: 1,
+                lifetime: None,
+                effect: None,
+            }],
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Food {
+    pub kind: String,
+    pub ticks_left: Option<u32>,
+}
+
+pub fn food(
+    position: Vec2,
+    size: f32,
+    atlas: &SpriteAtlas,
+    definition: &FoodDefinition,
+) -> impl Bundle {
+    (
+        Food {
+            kind: definition.name.clone(),
+            ticks_left: definition.lifetime,
+        },
+        cell(position, size),
+        tinted_atlas_sprite(atlas, definition.sprite.into(), definition.color),
+    )
+}
+
+pub fn replenish_food(
+    mut commands: Commands,
+    occupied: Query<
+        &Cell,
+        Or<(
+            With<CatHead>,
+            With<CatBody>,
+            With<CatTail>,
+            With<Food>,
+            With<Wall>,
+            With<Portal>,
+        )>,
+    >,
+    foods: Query<(), With<Food>>,
+    registry: Res<FoodRegistry>,
+    level: Res<Level>,
+    mut rng: ResMut<GameRng>,
+    size: Res<CellSize>,
+    atlas: Res<SpriteAtlas>,
+    mut board_cleared: EventWriter<BoardCleared>,
+    tuning: Res<Tuning>,
+) {
+    let mut occupied = occupied.iter().map(|cell| **cell).collect::<Vec<_>>();
+    let food_count = foods.iter().count();
+
+    for spawned in food_count..level.rules.food_count {
+        let Some(position) = random_free_cell(&occupied, level.size, &mut **rng) else {
+            if spawned == 0 {
+                board_cleared.write(BoardCleared);
+            }
+            return;
+        };
+
+        let Some(definition) = registry.choose(&mut **rng, &tuning, |definition| {
+            level.rules.lives || !matches!(definition.effect, Some(FoodEffect::ExtraLife))
+        }) else {
+            return;
+        };
+
+        occupied.push(position);
+        commands.spawn((
+            StateScoped(InGame::True),
+            food(position, **size, &atlas, definition),
+        ));
+    }
+}
+
+fn expire_food(mut commands: Commands, mut foods: Query<(Entity, &mut Food)>) {
+    for (entity, mut food) in &mut foods {
+        let Some(ticks_left) = food.ticks_left.as_mut() else {
+            continue;
+        };
+
+        *ticks_left = ticks_left.saturating_sub(1);
+        if *ticks_left == 0 {
+            commands.entity(entity).despawn();
+            commands.run_system_cached(replenish_food);
+        }
+    }
+}
+
+#[cfg(feature = "dev")]
+fn food_command(
+    In(args): In<ConsoleArgs>,
+    mut commands: Commands,
+    occupied: Query<
+        &Cell,
+        Or<(
+            With<CatHead>,
+            With<CatBody>,
+            With<CatTail>,
+            With<Food>,
+            With<Wall>,
+            With<Portal>,
+        )>,
+    >,
+    registry: Res<FoodRegistry>,
+    level: Res<Level>,
+    size: Res<CellSize>,
+    atlas: Res<SpriteAtlas>,
+    in_game: Res<State<InGame>>,
+) -> ConsoleResult {
+    let x = parse_arg::<i32>(&args, 0, "x")?;
+    let y = parse_arg::<i32>(&args, 1, "y")?;
+    let kind = args.get(2).map_or("fish", String::as_str);
+    if *in_game.get() != InGame::True {
+        return Err("no run in progress".into());
+    }
+    let Some(definition) = registry.get(kind) else {
+        return Err(format!("unknown food kind: {kind}"));
+    };
+
+    let position = IVec2::new(x, y);
+    if position.cmplt(IVec2::ZERO).any() || position.cmpge(level.size).any() {
+        return Err(format!("{x},{y} is outside the board"));
+    }
+    let position = position.as_vec2();
+    if occupied.iter().any(|cell| **cell == position) {
+        return Err(format!("{x},{y} is occupied"));
+    }
+
+    commands.spawn((
+        StateScoped(InGame::True),
+        food(position, **size, &atlas, definition),
+    ));
+    Ok(format!("placed {kind} at {x},{y}"))
 }
@@ -1,6 +1,10 @@
 use bevy::{prelude::*, window::WindowResized};
+use rand::Rng;
 
-use crate::game::{GameState, GameSystems};
+use crate::{
+    game::{GameState, GameSystems, level::Level},
+    theme::Theme,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<CellSize>();
@@ -23,8 +27,7 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-pub const GRID_CELLS: IVec2 = IVec2::new(20, 10);
-pub const GRID_CENTER: IVec2 = IVec2::new(GRID_CELLS.x / 2, GRID_CELLS.y / 2);
+pub const CELL_DEPTH: f32 = 2.0;
 
 #[derive(Resource, Default, Deref, DerefMut, Debug)]
 pub struct CellSize(f32);
@@ -35,22 +38,41 @@ pub struct Cell(pub Vec2);
 pub fn cell(position: Vec2, size: f32) -> impl Bundle {
     (
         Cell(position),
-        Transform::from_translation(Vec3::from((position * size, 2.0)))
+        Transform::from_translation(Vec3::from((position * size, CELL_DEPTH)))
             .with_scale(Vec3::splat(size)),
     )
 }
 
-fn move_camera_to_grid(mut camera: Single<&mut Transform, With<Camera2d>>, size: Res<CellSize>) {
-    camera.translation = Vec3::from(((GRID_CELLS.as_vec2() * **size / 2.0) - **size / 2.0, 0.0));
+pub fn board_cell(x: i32, y: i32, size: f32, theme: &Theme) -> impl Bundle {
+    (
+        cell(Vec2::new(x as f32, y as f32), size),
+        Sprite::from_color(theme.board_color(x, y), Vec2::ONE),
+    )
+}
+
+pub fn cell_coords(cell: Vec2) -> [i32; 2] {
+    [cell.x as i32, cell.y as i32]
+}
+
+pub fn move_camera_to_grid(
+    mut camera: Single<&mut Transform, With<Camera2d>>,
+    size: Res<CellSize>,
+    level: Res<Level>,
+) {
+    camera.translation = Vec3::from(((level.cells() * **size / 2.0) - **size / 2.0, 0.0));
 }
 
-fn setup_cell_size(window: Single<&Window>, mut size: ResMut<CellSize>) {
-    **size = (Vec2::new(window.width(), window.height()) / GRID_CELLS.as_vec2()).min_element();
+pub fn setup_cell_size(window: Single<&Window>, mut size: ResMut<CellSize>, level: Res<Level>) {
+    **size = (Vec2::new(window.width(), window.height()) / level.cells()).min_element();
 }
 
-fn handle_window_resize(mut resize_reader: EventReader<WindowResized>, mut size: ResMut<CellSize>) {
+fn handle_window_resize(
+    mut resize_reader: EventReader<WindowResized>,
+    mut size: ResMut<CellSize>,
+    level: Res<Level>,
+) {
     if let Some(e) = resize_reader.read().last() {
-        **size = (Vec2::new(e.width, e.height) / GRID_CELLS.as_vec2()).min_element();
+        **size = (Vec2::new(e.width, e.height) / level.cells()).min_element();
     }
 }
 
@@ -60,3 +82,16 @@ fn resize_cells(mut cells: Query<(&mut Transform, &Cell)>, size: Res<CellSize>)
         transform.scale = Vec3::splat(**size);
     }
 }
+
+pub fn random_free_cell(occupied: &[Vec2], cells: IVec2, rng: &mut impl Rng) -> Option<Vec2> {
+    let free = (0..cells.y)
+        .flat_map(|y| (0..cells.x).map(move |x| Vec2::new(x as f32, y as f32)))
+        .filter(|position| !occupied.contains(position))
+        .collect::<Vec<_>>();
+
+    if free.is_empty() {
+        None
+    } else {
+        Some(free[rng.random_range(0..free.len())])
+    }
+}
@@ -0,0 +1,122 @@
+use std::mem;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        GameState, GameSystems, GameTick, RestartRun,
+        cat::{Cat, Segments},
+        events::{CatCrashed, DirectionChanged, TickAdvanced},
+        food::Food,
+        grid::{Cell, cell_coords},
+        savegame::RunSaved,
+    },
+    profiles::{ActiveProfile, load_profile_data},
+    storage,
+};
+
+const HISTORY_FILE: &str = "history.json";
+const MAX_RUNS: usize = 50;
+const LENGTH_SAMPLE_TICKS: u64 = 10;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<RunHistory>();
+    app.add_systems(
+        PreUpdate,
+        load_profile_data::<RunHistory>(HISTORY_FILE).run_if(resource_changed::<ActiveProfile>),
+    );
+    app.init_resource::<CurrentRun>();
+
+    app.add_systems(
+        OnExit(GameState::None),
+        reset_current_run.in_set(GameSystems::Spawn),
+    );
+
+    app.add_systems(RestartRun, reset_current_run);
+
+    app.add_systems(GameTick, record_run.in_set(GameSystems::PostFixedUpdate));
+
+    app.add_systems(OnEnter(GameState::Over), archive_current_run);
+    app.add_systems(
+        OnEnter(GameState::None),
+        archive_current_run.run_if(not(resource_exists::<RunSaved>)),
+    );
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct RunRecord {
+    pub ticks: u64,
+    pub deaths: Vec<[i32; 2]>,
+    pub food_spawns: Vec<[i32; 2]>,
+    pub turns: Vec<[i32; 2]>,
+    pub lengths: Vec<usize>,
+}
+
+impl RunRecord {
+    pub fn peak_length(&self) -> usize {
+        self.lengths.iter().copied().max().unwrap_or_default()
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct RunHistory {
+    pub runs: Vec<RunRecord>,
+}
+
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+pub struct CurrentRun(RunRecord);
+
+fn reset_current_run(mut current_run: ResMut<CurrentRun>) {
+    **current_run = RunRecord::default();
+}
+
+fn record_run(
+    mut current_run: ResMut<CurrentRun>,
+    mut tick_advanced: EventReader<TickAdvanced>,
+    mut crashed: EventReader<CatCrashed>,
+    mut direction_changed: EventReader<DirectionChanged>,
+    spawned_food: Query<&Cell, Added<Food>>,
+    cat: Query<&Segments, With<Cat>>,
+) {
+    for event in crashed.read() {
+        current_run.deaths.push(cell_coords(event.cell));
+    }
+    for event in direction_changed.read() {
+        current_run.turns.push(cell_coords(event.cell));
+    }
+    for cell in &spawned_food {
+        current_run.food_spawns.push(cell_coords(**cell));
+    }
+    for event in tick_advanced.read() {
+        current_run.ticks = event.tick;
+        if event.tick % LENGTH_SAMPLE_TICKS == 0
+            && let Ok(segments) = cat.single()
+        {
+            current_run.lengths.push(segments.len());
+        }
+    }
+}
+
+fn archive_current_run(
+    mut current_run: ResMut<CurrentRun>,
+    mut history: ResMut<RunHistory>,
+    cat: Query<&Segments, With<Cat>>,
+    profile: Res<ActiveProfile>,
+) {
+    let mut run = mem::take(&mut **current_run);
+    if run.ticks == 0 {
+        return;
+    }
+
+    if let Ok(segments) = cat.single() {
+        run.lengths.push(segments.len());
+    }
+
+    history.runs.push(run);
+    let excess = history.runs.len().saturating_sub(MAX_RUNS);
+    history.runs.drain(..excess);
+    storage::save(**profile, HISTORY_FILE, &*history);
+}
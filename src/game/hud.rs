@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    GameMode, GameState, GameSystems, InGame,
+    campaign::{Campaign, CampaignRun, CampaignStage},
+    level::Level,
+    lives::Lives,
+    powerup::ActivePowerUps,
+    score::Score,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnExit(GameState::None),
+        spawn_hud.in_set(GameSystems::Spawn),
+    );
+
+    app.add_systems(
+        Update,
+        (
+            update_score_text.run_if(resource_changed::<Score>),
+            update_lives_text.run_if(resource_changed::<Lives>.or(resource_changed::<Level>)),
+            update_power_ups_text.run_if(resource_changed::<ActivePowerUps>),
+            update_goal_text.run_if(resource_changed::<CampaignRun>),
+        )
+            .in_set(GameSystems::Update),
+    );
+}
+
+#[derive(Component, Debug)]
+struct ScoreText;
+
+#[derive(Component, Debug)]
+struct LivesText;
+
+#[derive(Component, Debug)]
+struct PowerUpsText;
+
+#[derive(Component, Debug)]
+struct GoalText;
+
+fn spawn_hud(mut commands: Commands) {
+    commands.spawn((
+        StateScoped(InGame::True),
+        Node {
+            width: Val::Percent(100.0),
+            padding: UiRect::axes(Val::Px(20.0), Val::Px(10.0)),
+            column_gap: Val::Px(40.0),
+            ..default()
+        },
+        children![
+            (
+                ScoreText,
+                Text::default(),
+                TextFont::from_font_size(30.0),
+                TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+            ),
+            (
+                LivesText,
+                Text::default(),
+                TextFont::from_font_size(30.0),
+                TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+            ),
+            (
+                PowerUpsText,
+                Text::default(),
+                TextFont::from_font_size(30.0),
+                TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+            ),
+            (
+                GoalText,
+                Text::default(),
+                TextFont::from_font_size(30.0),
+                TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+            )
+        ],
+    ));
+}
+
+fn update_score_text(mut text: Single<&mut Text, With<ScoreText>>, score: Res<Score>) {
+    text.0 = format!("Score {}", **score);
+}
+
+fn update_lives_text(
+    text: Single<(&mut Text, &mut Visibility), With<LivesText>>,
+    lives: Res<Lives>,
+    level: Res<Level>,
+) {
+    let (mut text, mut visibility) = text.into_inner();
+    text.0 = format!("Lives {}", **lives);
+    *visibility = if level.rules.lives {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+}
+
+fn update_power_ups_text(
+    mut text: Single<&mut Text, With<PowerUpsText>>,
+    active_power_ups: Res<ActivePowerUps>,
+) {
+    text.0 = active_power_ups
+        .remaining()
+        .map(|(power_up, seconds)| format!("{} {:.1}", power_up.label(), seconds))
+        .collect::<Vec<_>>()
+        .join("  ");
+}
+
+fn update_goal_text(
+    mut text: Single<&mut Text, With<GoalText>>,
+    run: Res<CampaignRun>,
+    mode: Res<GameMode>,
+    campaign: Res<Campaign>,
+    stage: Res<CampaignStage>,
+) {
+    text.0 = match campaign.level(**stage) {
+        Some(level) if *mode == GameMode::Campaign => {
+            format!("{}: {}", level.goal.label(), level.goal.progress(&run))
+        }
+        _ => String::new(),
+    };
+}
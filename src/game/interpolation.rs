@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::{
+        GameState, GameSystems, GameTick,
+        cat::{handle_hit, move_cat},
+        grid::{Cell, CellSize},
+        level::Level,
+        ticktimer::TickTimer,
+    },
+    settings::Settings,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        GameTick,
+        (begin_motion.before(move_cat), end_motion.after(handle_hit))
+            .in_set(GameSystems::FixedUpdate),
+    );
+
+    app.add_systems(
+        Update,
+        interpolate_motion
+            .run_if(
+                in_state(GameState::Run).and(|settings: Res<Settings>| settings.smooth_movement),
+            )
+            .in_set(GameSystems::Update),
+    );
+
+    app.add_systems(OnEnter(GameState::Over), snap_motion);
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Motion {
+    from: Vec2,
+    from_rotation: Quat,
+    to_rotation: Quat,
+}
+
+impl Motion {
+    pub fn new(position: Vec2, rotation: Quat) -> Self {
+        Self {
+            from: position,
+            from_rotation: rotation,
+            to_rotation: rotation,
+        }
+    }
+
+    fn sample(&self, to: Vec2, t: f32, grid: Vec2) -> (Vec2, Quat) {
+        let delta = (to - self.from + grid / 2.0).rem_euclid(grid) - grid / 2.0;
+        let position = if delta.length_squared() > 1.0 {
+            if t < 0.5 { self.from } else { to }
+        } else if t < 0.5 {
+            self.from + delta * t
+        } else {
+            to - delta * (1.0 - t)
+        };
+
+        let rotation = if is_planar(self.from_rotation) && is_planar(self.to_rotation) {
+            self.from_rotation.slerp(self.to_rotation, t)
+        } else if t < 0.5 {
+            self.from_rotation
+        } else {
+            self.to_rotation
+        };
+
+        (position, rotation)
+    }
+}
+
+fn is_planar(rotation: Quat) -> bool {
+    rotation.x.abs() < f32::EPSILON && rotation.y.abs() < f32::EPSILON
+}
+
+fn begin_motion(mut segments: Query<(&Cell, &mut Motion)>) {
+    for (cell, mut motion) in &mut segments {
+        motion.from = **cell;
+        motion.from_rotation = motion.to_rotation;
+    }
+}
+
+fn end_motion(mut segments: Query<(&Transform, &mut Motion)>) {
+    for (transform, mut motion) in &mut segments {
+        motion.to_rotation = transform.rotation;
+    }
+}
+
+fn interpolate_motion(
+    mut segments: Query<(&Cell, &Motion, &mut Transform)>,
+    timer: Res<TickTimer>,
+    size: Res<CellSize>,
+    level: Res<Level>,
+) {
+    place_segments(&mut segments, timer.fraction(), **size, level.cells());
+}
+
+fn snap_motion(
+    mut segments: Query<(&Cell, &Motion, &mut Transform)>,
+    size: Res<CellSize>,
+    level: Res<Level>,
+) {
+    place_segments(&mut segments, 1.0, **size, level.cells());
+}
+
+fn place_segments(
+    segments: &mut Query<(&Cell, &Motion, &mut Transform)>,
+    t: f32,
+    size: f32,
+    grid: Vec2,
+) {
+    for (cell, motion, mut transform) in segments {
+        let (position, rotation) = motion.sample(**cell, t, grid);
+        transform.translation = Vec3::from((position * size, transform.translation.z));
+        transform.rotation = rotation;
+    }
+}
@@ -1,53 +1,304 @@
+use std::collections::HashMap;
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::game::{
-    GameState, GameSystems, InGame,
-    atlas::{AtlasSprite, SpriteAtlas},
-    cat::{CatBody, CatHead, CatTail, Direction, cat_body, cat_head, cat_tail},
-    food::{Food, food},
-    grid::{CellSize, GRID_CELLS, GRID_CENTER, cell},
+#[cfg(feature = "dev")]
+use crate::{
+    console::{ConsoleArgs, ConsoleExt, ConsoleResult, parse_arg},
+    game::events::LevelChanged,
+    screens::Screen,
+};
+use crate::{
+    game::{
+        GameMode, GameState, GameSystems, GameTick, InGame, RestartRun,
+        atlas::{AtlasSprite, SpriteAtlas},
+        campaign::{Campaign, CampaignStage},
+        cat::{Cat, CatHead, Direction, Segments, cat_body, cat_head, cat_tail, move_cat},
+        daily::DailyChallenge,
+        food::{Food, replenish_food},
+        grid::{Cell, CellSize, board_cell, cell, move_camera_to_grid, setup_cell_size},
+        ticktimer::SpeedUp,
+    },
+    settings::{Difficulty, Settings},
+    theme::Theme,
 };
 
+pub const CUSTOM_LEVEL_FILE: &str = "levels/custom.json";
+pub const CLASSIC_LEVEL: &str = "classic";
+
+const DEFAULT_GRID_CELLS: IVec2 = IVec2::new(20, 10);
+
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Level>();
+    app.init_resource::<LoadedLevels>();
+
+    app.add_systems(
+        OnExit(GameState::None),
+        prepare_level.in_set(GameSystems::Level),
+    );
     app.add_systems(
         OnExit(GameState::None),
         spawn_level.in_set(GameSystems::Spawn),
     );
 
     app.add_systems(
-        OnTransition {
-            exited: GameState::Over,
-            entered: GameState::Run,
-        },
-        reset_level,
+        RestartRun,
+        (
+            prepare_level,
+            setup_cell_size,
+            move_camera_to_grid,
+            reset_level,
+        )
+            .chain(),
+    );
+
+    app.add_systems(
+        GameTick,
+        apply_tiles.after(move_cat).in_set(GameSystems::FixedUpdate),
+    );
+
+    app.add_systems(
+        Update,
+        apply_theme.run_if(in_state(InGame::True).and(resource_changed::<Theme>)),
+    );
+
+    #[cfg(feature = "dev")]
+    app.add_systems(
+        Update,
+        (
+            reload_level.run_if(in_state(InGame::True).and(on_event::<LevelChanged>)),
+            apply_pending_level.run_if(resource_exists::<PendingLevel>),
+        ),
     );
+
+    #[cfg(feature = "dev")]
+    app.add_console_command("level", "level load <name>", level_command);
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct Rules {
+    pub lives: bool,
+    pub food_count: usize,
+    pub difficulty: Difficulty,
+}
+
+impl Rules {
+    pub fn from_settings(settings: &Settings) -> Self {
+        Self {
+            lives: settings.lives,
+            food_count: settings.food_count,
+            difficulty: settings.difficulty,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TileKind {
+    Boost,
+    Slow,
+}
+
+impl TileKind {
+    pub fn color(self) -> Color {
+        match self {
+            TileKind::Boost => Color::srgb_u8(0x30, 0x50, 0x80),
+            TileKind::Slow => Color::srgb_u8(0x50, 0x30, 0x60),
+        }
+    }
+
+    fn speed_up(self) -> SpeedUp {
+        match self {
+            TileKind::Boost => SpeedUp {
+                factor: 2.0,
+                ticks: 10,
+            },
+            TileKind::Slow => SpeedUp {
+                factor: 0.5,
+                ticks: 5,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Debug)]
+pub struct Tile {
+    pub cell: IVec2,
+    pub kind: TileKind,
+}
+
+#[derive(Resource, Asset, TypePath, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct Level {
+    pub size: IVec2,
+    pub walls: Vec<IVec2>,
+    #[serde(default)]
+    pub portals: Vec<[IVec2; 2]>,
+    #[serde(default)]
+    pub tiles: Vec<Tile>,
+    #[serde(default)]
+    pub script: Option<String>,
+    pub start: IVec2,
+    pub direction: Direction,
+    pub rules: Rules,
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Self {
+            size: DEFAULT_GRID_CELLS,
+            walls: Vec::new(),
+            portals: Vec::new(),
+            tiles: Vec::new(),
+            script: None,
+            start: DEFAULT_GRID_CELLS / 2,
+            direction: Direction::Right,
+            rules: Rules::from_settings(&Settings::default()),
+        }
+    }
+}
+
+impl Level {
+    pub fn cells(&self) -> Vec2 {
+        self.size.as_vec2()
+    }
+
+    pub fn wrap(&self, position: Vec2) -> Vec2 {
+        position.rem_euclid(self.cells())
+    }
+
+    pub fn spawn_cells(&self) -> [Vec2; 3] {
+        let start = self.start.as_vec2();
+        let back = -self.direction.to_vec();
+        [
+            start,
+            self.wrap(start + back),
+            self.wrap(start + back * 2.0),
+        ]
+    }
+
+    pub fn portal_exit(&self, position: Vec2) -> Option<Vec2> {
+        let position = position.as_ivec2();
+        self.portals.iter().find_map(|[a, b]| {
+            if *a == position {
+                Some(b.as_vec2())
+            } else if *b == position {
+                Some(a.as_vec2())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn portal_color(index: usize) -> Color {
+        Color::hsl(index as f32 * 67.0 % 360.0, 0.6, 0.45)
+    }
+}
+
+#[derive(Resource, Deref, DerefMut, Default, Debug)]
+pub struct LoadedLevels(HashMap<String, Level>);
+
+impl LoadedLevels {
+    pub fn classic(&self, settings: &Settings) -> Level {
+        Level {
+            rules: Rules::from_settings(settings),
+            ..self.get(CLASSIC_LEVEL).cloned().unwrap_or_default()
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Board;
+
+#[derive(Component, Debug)]
+pub struct Wall;
+
+#[derive(Component, Debug)]
+pub struct Portal;
+
+#[derive(Component, Debug)]
+pub struct Scenery;
+
+fn prepare_level(
+    mut level: ResMut<Level>,
+    mode: Res<GameMode>,
+    settings: Res<Settings>,
+    campaign: Res<Campaign>,
+    stage: Res<CampaignStage>,
+    daily: Res<DailyChallenge>,
+    levels: Res<LoadedLevels>,
+) {
+    match *mode {
+        GameMode::Classic => *level = levels.classic(&settings),
+        GameMode::Daily => *level = daily.level(),
+        GameMode::Custom => {}
+        GameMode::Campaign => {
+            if let Some(loaded) = campaign
+                .level(**stage)
+                .and_then(|campaign_level| levels.get(&campaign_level.id))
+            {
+                *level = loaded.clone();
+            }
+        }
+    }
 }
 
-fn spawn_level(mut commands: Commands, size: Res<CellSize>, atlas: Res<SpriteAtlas>) {
-    for y in 0..GRID_CELLS.y {
-        for x in 0..GRID_CELLS.x {
-            let position = Vec2::new(x as f32, y as f32);
+pub fn wall(position: Vec2, size: f32, theme: &Theme) -> impl Bundle {
+    (
+        Scenery,
+        Wall,
+        cell(position, size),
+        Sprite::from_color(theme.wall, Vec2::ONE),
+    )
+}
+
+fn spawn_level(
+    mut commands: Commands,
+    size: Res<CellSize>,
+    atlas: Res<SpriteAtlas>,
+    level: Res<Level>,
+    theme: Res<Theme>,
+) {
+    for y in 0..level.size.y {
+        for x in 0..level.size.x {
             commands.spawn((
                 StateScoped(InGame::True),
-                cell(position, **size),
-                Sprite::from_color(
-                    if (x + y) % 2 == 0 {
-                        Color::srgb_u8(0x10, 0x10, 0x10)
-                    } else {
-                        Color::srgb_u8(0x20, 0x20, 0x20)
-                    },
-                    Vec2::ONE,
-                ),
+                Board,
+                board_cell(x, y, **size, &theme),
             ));
         }
     }
 
-    let cat_positions = [
-        GRID_CENTER.as_vec2(),
-        GRID_CENTER.as_vec2() + Direction::Left.to_vec(),
-        GRID_CENTER.as_vec2() + Direction::Left.to_vec() * 2.0,
-    ];
-    let cat_directions = [Direction::Right, Direction::Right, Direction::Right];
+    for wall in &level.walls {
+        commands.spawn((
+            StateScoped(InGame::True),
+            self::wall(wall.as_vec2(), **size, &theme),
+        ));
+    }
+
+    for (index, portal) in level.portals.iter().enumerate() {
+        for end in portal {
+            commands.spawn((
+                StateScoped(InGame::True),
+                Scenery,
+                Portal,
+                cell(end.as_vec2(), **size),
+                Sprite::from_color(Level::portal_color(index), Vec2::ONE),
+            ));
+        }
+    }
+
+    for tile in &level.tiles {
+        commands.spawn((
+            StateScoped(InGame::True),
+            Scenery,
+            cell(tile.cell.as_vec2(), **size),
+            Sprite::from_color(tile.kind.color(), Vec2::ONE),
+        ));
+    }
+
+    let cat_positions = level.spawn_cells();
+    let cat_directions = [level.direction; 3];
     let cat_atlas_indicies = [
         AtlasSprite::Head1.into(),
         AtlasSprite::Body1.into(),
@@ -56,63 +307,144 @@ fn spawn_level(mut commands: Commands, size: Res<CellSize>, atlas: Res<SpriteAtl
 
     commands.spawn((
         StateScoped(InGame::True),
-        cat_head(
-            cat_positions[0],
-            cat_directions[0],
-            **size,
-            &atlas,
-            cat_atlas_indicies[0],
-        ),
+        Cat,
+        Segments::spawn((
+            Spawn(cat_head(
+                cat_positions[0],
+                cat_directions[0],
+                **size,
+                &atlas,
+                cat_atlas_indicies[0],
+            )),
+            Spawn(cat_body(
+                cat_positions[1],
+                cat_directions[1],
+                **size,
+                &atlas,
+                cat_atlas_indicies[1],
+            )),
+            Spawn(cat_tail(
+                cat_positions[2],
+                cat_directions[2],
+                **size,
+                &atlas,
+                cat_atlas_indicies[2],
+            )),
+        )),
     ));
 
-    commands.spawn((
-        StateScoped(InGame::True),
-        cat_body(
-            cat_positions[1],
-            cat_directions[1],
-            **size,
-            &atlas,
-            cat_atlas_indicies[1],
-        ),
-    ));
+    commands.run_system_cached(replenish_food);
+}
 
-    commands.spawn((
-        StateScoped(InGame::True),
-        cat_tail(
-            cat_positions[2],
-            cat_directions[2],
-            **size,
-            &atlas,
-            cat_atlas_indicies[2],
-        ),
-    ));
+pub(super) fn reset_level(
+    mut commands: Commands,
+    entities: Query<Entity, Or<(With<Cat>, With<Food>, With<Scenery>, With<Board>)>>,
+) {
+    for entity in &entities {
+        commands.entity(entity).despawn();
+    }
+    commands.run_system_cached(spawn_level);
+}
 
-    let mut position = Vec2::new(
-        rand::random_range(0..GRID_CELLS.x) as f32,
-        rand::random_range(0..GRID_CELLS.y) as f32,
-    );
+fn apply_theme(
+    mut board: Query<(&Cell, &mut Sprite), With<Board>>,
+    mut walls: Query<&mut Sprite, (With<Wall>, Without<Board>)>,
+    theme: Res<Theme>,
+) {
+    for (cell, mut sprite) in &mut board {
+        sprite.color = theme.board_color(cell.x as i32, cell.y as i32);
+    }
+    for mut sprite in &mut walls {
+        sprite.color = theme.wall;
+    }
+}
+
+#[cfg(feature = "dev")]
+fn current_level_changed(
+    mut level_changed: EventReader<LevelChanged>,
+    mode: Res<GameMode>,
+    campaign: Res<Campaign>,
+    stage: Res<CampaignStage>,
+) -> bool {
+    let current = match *mode {
+        GameMode::Classic => Some(CLASSIC_LEVEL),
+        GameMode::Campaign => campaign.level(**stage).map(|level| level.id.as_str()),
+        GameMode::Daily | GameMode::Custom => None,
+    };
+    level_changed
+        .read()
+        .any(|event| Some(event.id.as_str()) == current)
+}
 
-    while cat_positions.contains(&position) {
-        position = Vec2::new(
-            rand::random_range(0..GRID_CELLS.x) as f32,
-            rand::random_range(0..GRID_CELLS.y) as f32,
-        );
+#[cfg(feature = "dev")]
+fn reload_level(world: &mut World) -> Result {
+    if world.run_system_cached(current_level_changed)? {
+        info!("current level changed on disk, respawning");
+        world.run_system_cached(prepare_level)?;
+        world.run_system_cached(setup_cell_size)?;
+        world.run_system_cached(move_camera_to_grid)?;
+        world.run_system_cached(reset_level)?;
     }
+    Ok(())
+}
 
-    commands.spawn((
-        StateScoped(InGame::True),
-        food(position, **size, &atlas, AtlasSprite::Fish.into()),
-    ));
+fn apply_tiles(mut commands: Commands, head: Single<&Cell, With<CatHead>>, level: Res<Level>) {
+    let position = head.as_ivec2();
+    if let Some(tile) = level.tiles.iter().find(|tile| tile.cell == position) {
+        commands.insert_resource(tile.kind.speed_up());
+    }
+}
+
+#[cfg(feature = "dev")]
+#[derive(Resource, Debug)]
+struct PendingLevel {
+    name: String,
+    handle: Handle<Level>,
 }
 
-fn reset_level(
+#[cfg(feature = "dev")]
+fn level_command(
+    In(args): In<ConsoleArgs>,
     mut commands: Commands,
-    snake: Query<Entity, Or<(With<CatHead>, With<CatBody>, With<CatTail>)>>,
-    food: Single<Entity, With<Food>>,
+    asset_server: Res<AssetServer>,
+) -> ConsoleResult {
+    let action = parse_arg::<String>(&args, 0, "action")?;
+    if action != "load" {
+        return Err(format!("unknown action: {action}"));
+    }
+    let name = parse_arg::<String>(&args, 1, "name")?;
+    commands.insert_resource(PendingLevel {
+        handle: asset_server.load(format!("levels/{name}.json")),
+        name: name.clone(),
+    });
+    Ok(format!("loading level {name}"))
+}
+
+#[cfg(feature = "dev")]
+fn apply_pending_level(
+    mut commands: Commands,
+    pending: Res<PendingLevel>,
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<Level>>,
+    mut level: ResMut<Level>,
+    mut mode: ResMut<GameMode>,
+    in_game: Res<State<InGame>>,
+    mut next_screen: ResMut<NextState<Screen>>,
 ) {
-    for entity in snake.iter() {
-        commands.entity(entity).despawn();
+    let Some(loaded) = assets.get(&pending.handle) else {
+        if asset_server.load_state(&pending.handle).is_failed() {
+            warn!("could not load level {}", pending.name);
+            commands.remove_resource::<PendingLevel>();
+        }
+        return;
+    };
+
+    *level = loaded.clone();
+    *mode = GameMode::Custom;
+    if *in_game.get() == InGame::True {
+        commands.run_schedule(RestartRun);
+        next_screen.set(Screen::Gameplay);
     }
-    commands.entity(*food).despawn();
-    commands.run_system_cached(spawn_level);
+    info!("loaded level {}", pending.name);
+    commands.remove_resource::<PendingLevel>();
 }
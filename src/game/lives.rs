@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use crate::game::{
+    GameState, GameSystems, GameTick, RestartRun,
+    cat::{CatBody, CatHead, CatTail, handle_hit},
+    level::Level,
+};
+
+#[cfg(feature = "dev")]
+use crate::console::{ConsoleArgs, ConsoleExt, ConsoleResult, parse_arg};
+
+const STARTING_LIVES: u32 = 3;
+const MAX_LIVES: u32 = 5;
+const INVULNERABLE_TICKS: u32 = 20;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Lives>();
+
+    app.add_systems(
+        OnExit(GameState::None),
+        reset_lives.in_set(GameSystems::Spawn),
+    );
+
+    app.add_systems(RestartRun, reset_lives);
+
+    app.add_systems(
+        GameTick,
+        blink_invulnerable
+            .after(handle_hit)
+            .in_set(GameSystems::FixedUpdate),
+    );
+
+    #[cfg(feature = "dev")]
+    app.add_console_command("god", "god <on|off>", god_command);
+}
+
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+pub struct Lives(u32);
+
+impl Lives {
+    pub fn gain(&mut self) {
+        self.0 = (self.0 + 1).min(MAX_LIVES);
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Invulnerable {
+    pub ticks: u32,
+}
+
+impl Invulnerable {
+    pub const PERMANENT: u32 = u32::MAX;
+}
+
+impl Default for Invulnerable {
+    fn default() -> Self {
+        Self {
+            ticks: INVULNERABLE_TICKS,
+        }
+    }
+}
+
+fn reset_lives(level: Res<Level>, mut lives: ResMut<Lives>) {
+    **lives = if level.rules.lives { STARTING_LIVES } else { 0 };
+}
+
+fn blink_invulnerable(
+    mut commands: Commands,
+    head: Single<(Entity, &mut Invulnerable), With<CatHead>>,
+    mut segments: Query<&mut Visibility, Or<(With<CatHead>, With<CatBody>, With<CatTail>)>>,
+) {
+    let (entity, mut invulnerable) = head.into_inner();
+    if invulnerable.ticks != Invulnerable::PERMANENT {
+        invulnerable.ticks = invulnerable.ticks.saturating_sub(1);
+    }
+
+    let visibility = if invulnerable.ticks == 0 {
+        commands.entity(entity).remove::<Invulnerable>();
+        Visibility::Inherited
+    } else if invulnerable.ticks % 2 == 0 {
+        Visibility::Hidden
+    } else {
+        Visibility::Inherited
+    };
+
+    for mut segment in &mut segments {
+        *segment = visibility;
+    }
+}
+
+#[cfg(feature = "dev")]
+fn god_command(
+    In(args): In<ConsoleArgs>,
+    mut commands: Commands,
+    head: Query<Entity, With<CatHead>>,
+) -> ConsoleResult {
+    let enabled = match parse_arg::<String>(&args, 0, "on|off")?.as_str() {
+        "on" => true,
+        "off" => false,
+        other => return Err(format!("expected on or off, got {other}")),
+    };
+    let Ok(head) = head.single() else {
+        return Err("no cat in play".into());
+    };
+
+    if enabled {
+        commands.entity(head).insert(Invulnerable {
+            ticks: Invulnerable::PERMANENT,
+        });
+        Ok("god mode on".into())
+    } else {
+        commands.entity(head).remove::<Invulnerable>();
+        Ok("god mode off".into())
+    }
+}
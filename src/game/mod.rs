@@ -1,50 +1,129 @@
-mod atlas;
-mod cat;
+pub mod achievements;
+pub mod atlas;
+pub mod campaign;
+pub mod cat;
 mod control;
-mod food;
-mod grid;
-mod level;
-mod ticktimer;
+pub mod countdown;
+pub mod daily;
+#[cfg(feature = "dev")]
+mod debug;
+pub mod events;
+pub mod food;
+pub mod grid;
+pub mod history;
+mod hud;
+mod interpolation;
+pub mod level;
+pub mod lives;
+mod powerup;
+mod rng;
+pub mod savegame;
+pub mod score;
+mod script;
+pub mod stats;
+mod telemetry;
+pub mod ticktimer;
+pub mod tuning;
 
-use bevy::prelude::*;
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+use serde::{Deserialize, Serialize};
 
 use crate::game::ticktimer::tick_passed;
+#[cfg(feature = "dev")]
+use crate::{
+    console::{ConsoleArgs, ConsoleExt, ConsoleResult, parse_arg},
+    screens::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         atlas::plugin,
+        events::plugin,
         ticktimer::plugin,
+        tuning::plugin,
         grid::plugin,
         control::plugin,
         cat::plugin,
+        food::plugin,
         level::plugin,
+        lives::plugin,
+        powerup::plugin,
+        score::plugin,
+        hud::plugin,
+        interpolation::plugin,
     ));
+    app.add_plugins((
+        telemetry::plugin,
+        history::plugin,
+        achievements::plugin,
+        stats::plugin,
+        rng::plugin,
+        daily::plugin,
+        campaign::plugin,
+        savegame::plugin,
+        countdown::plugin,
+        script::plugin,
+    ));
+    #[cfg(feature = "dev")]
+    app.add_plugins(debug::plugin);
 
+    app.add_systems(
+        OnTransition {
+            exited: GameState::Over,
+            entered: GameState::Run,
+        },
+        restart_run,
+    );
+    app.add_systems(
+        OnTransition {
+            exited: GameState::Over,
+            entered: GameState::Countdown,
+        },
+        restart_run,
+    );
+
+    #[cfg(feature = "dev")]
+    app.add_console_command("state", "state <run|pause|over>", state_command);
+
+    app.init_resource::<GameMode>();
     app.init_state::<GameState>();
     app.init_state::<InGame>();
 
     app.configure_sets(
         OnExit(GameState::None),
-        (GameSystems::Resources, GameSystems::Spawn).chain(),
+        (
+            GameSystems::Level,
+            GameSystems::Resources,
+            GameSystems::Spawn,
+        )
+            .chain(),
     );
 
     app.configure_sets(
         Update,
         (
             (
-                GameSystems::TickTimers,
-                GameSystems::Input,
-                (GameSystems::PreFixedUpdate, GameSystems::FixedUpdate)
-                    .chain()
-                    .run_if(tick_passed()),
+                GameSystems::TickTimers.run_if(in_state(GameState::Run)),
+                GameSystems::Input
+                    .run_if(in_state(GameState::Run).or(in_state(GameState::Countdown))),
+                GameSystems::Ticks.run_if(in_state(GameState::Run).and(tick_passed())),
             )
-                .chain()
-                .run_if(in_state(GameState::Run)),
+                .chain(),
             GameSystems::Update,
         )
             .chain()
             .run_if(not(in_state(GameState::None))),
     );
+
+    app.configure_sets(
+        GameTick,
+        (
+            GameSystems::PreFixedUpdate,
+            GameSystems::FixedUpdate,
+            GameSystems::PostFixedUpdate,
+        )
+            .chain(),
+    );
 }
 
 #[derive(States, Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
@@ -52,11 +131,40 @@ pub(super) fn plugin(app: &mut App) {
 pub enum GameState {
     #[default]
     None,
+    Countdown,
     Run,
     Pause,
     Over,
 }
 
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    #[default]
+    Classic,
+    Daily,
+    Custom,
+    Campaign,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 4] = [
+        GameMode::Classic,
+        GameMode::Daily,
+        GameMode::Custom,
+        GameMode::Campaign,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GameMode::Classic => "Classic",
+            GameMode::Daily => "Daily",
+            GameMode::Custom => "Custom",
+            GameMode::Campaign => "Campaign",
+        }
+    }
+}
+
 #[derive(States, Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
 #[states(scoped_entities)]
 pub enum InGame {
@@ -65,13 +173,49 @@ pub enum InGame {
     True,
 }
 
+#[derive(ScheduleLabel, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct RestartRun;
+
+#[derive(ScheduleLabel, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct GameTick;
+
+fn restart_run(world: &mut World) {
+    world.run_schedule(RestartRun);
+}
+
+#[cfg(feature = "dev")]
+fn state_command(
+    In(args): In<ConsoleArgs>,
+    screen: Res<State<Screen>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) -> ConsoleResult {
+    if !matches!(
+        screen.get(),
+        Screen::Gameplay | Screen::Pause | Screen::Over
+    ) {
+        return Err("no run in progress".into());
+    }
+
+    let state = parse_arg::<String>(&args, 0, "run|pause|over")?;
+    next_screen.set(match state.as_str() {
+        "run" => Screen::Gameplay,
+        "pause" => Screen::Pause,
+        "over" => Screen::Over,
+        other => return Err(format!("unknown state: {other}")),
+    });
+    Ok(format!("switching to {state}"))
+}
+
 #[derive(SystemSet, Hash, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum GameSystems {
+    Level,
     Resources,
     Spawn,
     TickTimers,
     Input,
+    Ticks,
     PreFixedUpdate,
     FixedUpdate,
+    PostFixedUpdate,
     Update,
 }
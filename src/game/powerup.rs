@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{
+    GameState, GameSystems, GameTick, RestartRun,
+    cat::{CatBody, CatHead, CatTail, move_cat, open_mouth_if_near_food},
+    food::Food,
+    grid::{Cell, CellSize},
+    level::{Level, Portal, Wall},
+};
+
+pub const SLOW_MOTION_SCALE: f32 = 2.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ActivePowerUps>();
+
+    app.add_systems(Update, advance_power_ups.in_set(GameSystems::TickTimers));
+    app.add_systems(
+        GameTick,
+        pull_food_with_magnet
+            .run_if(|active: Res<ActivePowerUps>| active.contains(PowerUp::Magnet))
+            .after(move_cat)
+            .before(open_mouth_if_near_food)
+            .in_set(GameSystems::FixedUpdate),
+    );
+
+    app.add_systems(OnEnter(GameState::Over), clear_power_ups);
+    app.add_systems(OnEnter(GameState::None), clear_power_ups);
+    app.add_systems(RestartRun, clear_power_ups);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerUp {
+    SlowMotion,
+    Ghost,
+    Magnet,
+}
+
+impl PowerUp {
+    pub fn label(self) -> &'static str {
+        match self {
+            PowerUp::SlowMotion => "Slow",
+            PowerUp::Ghost => "Ghost",
+            PowerUp::Magnet => "Magnet",
+        }
+    }
+
+    pub fn default_rule(self) -> PowerUpRule {
+        match self {
+            PowerUp::SlowMotion => PowerUpRule {
+                seconds: 5.0,
+                stacking: Stacking::Extend,
+            },
+            PowerUp::Ghost => PowerUpRule {
+                seconds: 4.0,
+                stacking: Stacking::Refresh,
+            },
+            PowerUp::Magnet => PowerUpRule {
+                seconds: 8.0,
+                stacking: Stacking::Ignore,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Stacking {
+    Refresh,
+    Extend,
+    Ignore,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+pub struct PowerUpRule {
+    pub seconds: f32,
+    pub stacking: Stacking,
+}
+
+#[derive(Resource, Clone, Default, Serialize, Deserialize, Debug)]
+pub struct ActivePowerUps {
+    timers: Vec<(PowerUp, Timer)>,
+}
+
+impl ActivePowerUps {
+    pub fn contains(&self, power_up: PowerUp) -> bool {
+        self.timers.iter().any(|(active, _)| *active == power_up)
+    }
+
+    pub fn remaining(&self) -> impl Iterator<Item = (PowerUp, f32)> {
+        self.timers
+            .iter()
+            .map(|(power_up, timer)| (*power_up, timer.remaining_secs()))
+    }
+
+    pub fn activate(&mut self, power_up: PowerUp, rule: PowerUpRule) {
+        let Some((_, timer)) = self
+            .timers
+            .iter_mut()
+            .find(|(active, _)| *active == power_up)
+        else {
+            self.timers
+                .push((power_up, Timer::from_seconds(rule.seconds, TimerMode::Once)));
+            return;
+        };
+
+        match rule.stacking {
+            Stacking::Refresh => timer.reset(),
+            Stacking::Extend => {
+                let duration = timer.remaining_secs() + rule.seconds;
+                timer.set_duration(Duration::from_secs_f32(duration));
+                timer.reset();
+            }
+            Stacking::Ignore => {}
+        }
+    }
+}
+
+fn advance_power_ups(time: Res<Time>, mut active: ResMut<ActivePowerUps>) {
+    if active.timers.is_empty() {
+        return;
+    }
+
+    for (_, timer) in &mut active.timers {
+        timer.tick(time.delta());
+    }
+    active.timers.retain(|(_, timer)| !timer.finished());
+}
+
+fn clear_power_ups(mut active: ResMut<ActivePowerUps>) {
+    active.timers.clear();
+}
+
+fn pull_food_with_magnet(
+    head: Single<&Cell, With<CatHead>>,
+    rest: Query<
+        &Cell,
+        (
+            Or<(With<CatBody>, With<CatTail>, With<Wall>, With<Portal>)>,
+            Without<Food>,
+        ),
+    >,
+    mut foods: Query<(&mut Cell, &mut Transform), (With<Food>, Without<CatHead>)>,
+    size: Res<CellSize>,
+    level: Res<Level>,
+) {
+    let grid = level.cells();
+    let mut occupied = rest
+        .iter()
+        .chain(foods.iter().map(|(cell, _)| cell))
+        .map(|cell| **cell)
+        .collect::<Vec<_>>();
+
+    for (mut cell, mut transform) in &mut foods {
+        let delta = (***head - **cell + grid / 2.0).rem_euclid(grid) - grid / 2.0;
+        if delta == Vec2::ZERO {
+            continue;
+        }
+
+        let step = if delta.x.abs() >= delta.y.abs() {
+            Vec2::new(delta.x.signum(), 0.0)
+        } else {
+            Vec2::new(0.0, delta.y.signum())
+        };
+        let next = (**cell + step).rem_euclid(grid);
+        if occupied.contains(&next) {
+            continue;
+        }
+
+        occupied.retain(|position| *position != **cell);
+        occupied.push(next);
+        **cell = next;
+        transform.translation = Vec3::from((next * **size, transform.translation.z));
+    }
+}
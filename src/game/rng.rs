@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+#[cfg(feature = "dev")]
+use crate::console::{ConsoleArgs, ConsoleExt, ConsoleResult, parse_arg};
+use crate::game::{GameMode, GameState, GameSystems, RestartRun, daily::DailyChallenge};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(GameRng(ChaCha8Rng::seed_from_u64(0)));
+
+    app.add_systems(OnExit(GameState::None), seed_rng.in_set(GameSystems::Level));
+
+    app.add_systems(RestartRun, seed_rng);
+
+    #[cfg(feature = "dev")]
+    app.add_console_command("seed", "seed <value>", seed_command);
+}
+
+#[derive(Resource, Deref, DerefMut, Debug)]
+pub struct GameRng(pub ChaCha8Rng);
+
+fn seed_rng(mut rng: ResMut<GameRng>, mode: Res<GameMode>, daily: Res<DailyChallenge>) {
+    let seed = match *mode {
+        GameMode::Classic | GameMode::Custom | GameMode::Campaign => rand::random(),
+        GameMode::Daily => daily.seed(),
+    };
+    **rng = ChaCha8Rng::seed_from_u64(seed);
+}
+
+#[cfg(feature = "dev")]
+fn seed_command(In(args): In<ConsoleArgs>, mut rng: ResMut<GameRng>) -> ConsoleResult {
+    let seed = parse_arg::<u64>(&args, 0, "value")?;
+    **rng = ChaCha8Rng::seed_from_u64(seed);
+    Ok(format!("rng seeded with {seed}"))
+}
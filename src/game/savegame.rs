@@ -0,0 +1,278 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        GameMode, GameState, GameSystems, InGame,
+        atlas::SpriteAtlas,
+        campaign::{CampaignRun, CampaignStage},
+        cat::{Cat, Direction, SegmentOf, Segments, cat_body, cat_head, cat_tail},
+        control::InputBuffer,
+        daily::{DailyAttempt, DailyChallenge},
+        food::{Food, FoodRegistry, food},
+        grid::{Cell, CellSize},
+        history::{CurrentRun, RunRecord},
+        level::Level,
+        lives::{Invulnerable, Lives},
+        powerup::ActivePowerUps,
+        rng::GameRng,
+        score::Score,
+        stats::RunTally,
+        ticktimer::{SpeedUp, Tick, TickTimer},
+    },
+    profiles::ActiveProfile,
+    storage,
+};
+
+const SAVE_FILE: &str = "savegame.json";
+const SAVE_VERSION: u32 = 2;
+const RULES_VERSION: u32 = 1;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnExit(GameState::None),
+        (
+            restore_level
+                .after(GameSystems::Level)
+                .before(GameSystems::Resources),
+            restore_game.after(GameSystems::Spawn),
+        )
+            .run_if(resource_exists::<PendingResume>),
+    );
+    app.add_systems(OnExit(GameState::None), clear_run_saved);
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct SaveHeader {
+    version: u32,
+    rules_version: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct SavedSegment {
+    cell: Vec2,
+    direction: Direction,
+    sprite: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct SavedFood {
+    kind: String,
+    cell: Vec2,
+    ticks_left: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SaveGame {
+    #[serde(flatten)]
+    header: SaveHeader,
+    pub mode: GameMode,
+    pub campaign_stage: usize,
+    level: Level,
+    segments: Vec<SavedSegment>,
+    invulnerable_ticks: Option<u32>,
+    food: Vec<SavedFood>,
+    score: u32,
+    lives: u32,
+    tick: u64,
+    tick_elapsed: f32,
+    rng: ChaCha8Rng,
+    speed_up: Option<SpeedUp>,
+    power_ups: ActivePowerUps,
+    input_buffer: InputBuffer,
+    campaign_run: CampaignRun,
+    tally: RunTally,
+    history: RunRecord,
+    daily_attempt: Option<DailyChallenge>,
+}
+
+#[derive(Resource, Debug)]
+pub struct PendingResume(pub SaveGame);
+
+#[derive(Resource, Debug)]
+pub struct RunSaved;
+
+pub fn load_save(profile: Option<u32>) -> Option<SaveGame> {
+    let header = storage::load::<SaveHeader>(profile, SAVE_FILE)?;
+    if header.version != SAVE_VERSION || header.rules_version != RULES_VERSION {
+        warn!(
+            "ignoring saved game with version {}.{}, expected {SAVE_VERSION}.{RULES_VERSION}",
+            header.version, header.rules_version
+        );
+        return None;
+    }
+    storage::load(profile, SAVE_FILE)
+}
+
+pub fn discard_save(profile: Option<u32>) {
+    storage::remove(profile, SAVE_FILE);
+}
+
+pub fn save_game(
+    mut commands: Commands,
+    cat: Single<&Segments, With<Cat>>,
+    segments: Query<(&Cell, &Direction, &Sprite, Option<&Invulnerable>)>,
+    foods: Query<(&Food, &Cell)>,
+    (score, lives, tick, timer): (Res<Score>, Res<Lives>, Res<Tick>, Res<TickTimer>),
+    (rng, speed_up, power_ups, input_buffer): (
+        Res<GameRng>,
+        Option<Res<SpeedUp>>,
+        Res<ActivePowerUps>,
+        Res<InputBuffer>,
+    ),
+    (mode, stage, campaign_run, level): (
+        Res<GameMode>,
+        Res<CampaignStage>,
+        Res<CampaignRun>,
+        Res<Level>,
+    ),
+    (tally, current_run, daily_attempt): (Res<RunTally>, Res<CurrentRun>, Res<DailyAttempt>),
+    profile: Res<ActiveProfile>,
+) {
+    let mut invulnerable_ticks = None;
+    let segments = segments
+        .iter_many(cat.iter())
+        .map(|(cell, direction, sprite, invulnerable)| {
+            if let Some(invulnerable) = invulnerable {
+                invulnerable_ticks = Some(invulnerable.ticks);
+            }
+            SavedSegment {
+                cell: **cell,
+                direction: *direction,
+                sprite: sprite.texture_atlas.as_ref().map_or(0, |atlas| atlas.index),
+            }
+        })
+        .collect();
+
+    let save = SaveGame {
+        header: SaveHeader {
+            version: SAVE_VERSION,
+            rules_version: RULES_VERSION,
+        },
+        mode: *mode,
+        campaign_stage: **stage,
+        level: level.clone(),
+        segments,
+        invulnerable_ticks,
+        food: foods
+            .iter()
+            .map(|(food, cell)| SavedFood {
+                kind: food.kind.clone(),
+                cell: **cell,
+                ticks_left: food.ticks_left,
+            })
+            .collect(),
+        score: **score,
+        lives: **lives,
+        tick: **tick,
+        tick_elapsed: timer.elapsed_secs(),
+        rng: rng.0.clone(),
+        speed_up: speed_up.map(|speed_up| speed_up.clone()),
+        power_ups: power_ups.clone(),
+        input_buffer: input_buffer.clone(),
+        campaign_run: campaign_run.clone(),
+        tally: tally.clone(),
+        history: current_run.clone(),
+        daily_attempt: **daily_attempt,
+    };
+    storage::save(**profile, SAVE_FILE, &save);
+    commands.insert_resource(RunSaved);
+}
+
+fn clear_run_saved(mut commands: Commands) {
+    commands.remove_resource::<RunSaved>();
+}
+
+fn restore_level(pending: Res<PendingResume>, mut level: ResMut<Level>) {
+    *level = pending.0.level.clone();
+}
+
+fn restore_game(
+    mut commands: Commands,
+    entities: Query<Entity, Or<(With<Cat>, With<Food>)>>,
+    size: Res<CellSize>,
+    atlas: Res<SpriteAtlas>,
+    registry: Res<FoodRegistry>,
+    (mut score, mut lives, mut tick, mut timer): (
+        ResMut<Score>,
+        ResMut<Lives>,
+        ResMut<Tick>,
+        ResMut<TickTimer>,
+    ),
+    (mut rng, mut power_ups, mut input_buffer, mut campaign_run): (
+        ResMut<GameRng>,
+        ResMut<ActivePowerUps>,
+        ResMut<InputBuffer>,
+        ResMut<CampaignRun>,
+    ),
+    (mut tally, mut current_run, mut daily_attempt, mut challenge): (
+        ResMut<RunTally>,
+        ResMut<CurrentRun>,
+        ResMut<DailyAttempt>,
+        ResMut<DailyChallenge>,
+    ),
+    pending: Res<PendingResume>,
+) {
+    let save = &pending.0;
+    for entity in &entities {
+        commands.entity(entity).despawn();
+    }
+
+    commands
+        .spawn((StateScoped(InGame::True), Cat))
+        .with_related_entities::<SegmentOf>(|parent| {
+            let last = save.segments.len().saturating_sub(1);
+            for (index, segment) in save.segments.iter().enumerate() {
+                let (cell, direction, sprite) = (segment.cell, segment.direction, segment.sprite);
+                if index == 0 {
+                    let mut head = parent.spawn(cat_head(cell, direction, **size, &atlas, sprite));
+                    if let Some(ticks) = save.invulnerable_ticks {
+                        head.insert(Invulnerable { ticks });
+                    }
+                } else if index == last {
+                    parent.spawn(cat_tail(cell, direction, **size, &atlas, sprite));
+                } else {
+                    parent.spawn(cat_body(cell, direction, **size, &atlas, sprite));
+                }
+            }
+        });
+
+    for saved in &save.food {
+        let Some(definition) = registry.get(&saved.kind) else {
+            continue;
+        };
+        commands
+            .spawn((
+                StateScoped(InGame::True),
+                food(saved.cell, **size, &atlas, definition),
+            ))
+            .insert(Food {
+                kind: saved.kind.clone(),
+                ticks_left: saved.ticks_left,
+            });
+    }
+
+    **score = save.score;
+    **lives = save.lives;
+    **tick = save.tick;
+    timer.set_elapsed(Duration::from_secs_f32(save.tick_elapsed));
+    rng.0 = save.rng.clone();
+    match &save.speed_up {
+        Some(speed_up) => commands.insert_resource(speed_up.clone()),
+        None => commands.remove_resource::<SpeedUp>(),
+    }
+    *power_ups = save.power_ups.clone();
+    *input_buffer = save.input_buffer.clone();
+    *campaign_run = save.campaign_run.clone();
+    *tally = save.tally.clone();
+    **current_run = save.history.clone();
+    **daily_attempt = save.daily_attempt;
+    if let Some(attempt) = save.daily_attempt {
+        *challenge = attempt;
+    }
+
+    commands.remove_resource::<PendingResume>();
+}
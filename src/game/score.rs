@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{GameMode, GameState, GameSystems, RestartRun, events::FoodEaten, food::FoodRegistry},
+    profiles::{ActiveProfile, load_profile_data},
+    storage,
+};
+
+const HIGH_SCORES_FILE: &str = "high_scores.json";
+const MAX_HIGH_SCORES: usize = 10;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Score>();
+    app.init_resource::<HighScores>();
+    app.add_systems(
+        PreUpdate,
+        load_profile_data::<HighScores>(HIGH_SCORES_FILE).run_if(resource_changed::<ActiveProfile>),
+    );
+
+    app.add_systems(
+        OnExit(GameState::None),
+        reset_score.in_set(GameSystems::Spawn),
+    );
+
+    app.add_systems(RestartRun, reset_score);
+
+    app.add_systems(Update, add_food_points.in_set(GameSystems::Update));
+    app.add_systems(
+        OnEnter(GameState::Over),
+        record_high_score.run_if(resource_equals(GameMode::Classic)),
+    );
+}
+
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+pub struct Score(u32);
+
+#[derive(Resource, Serialize, Deserialize, Default, Deref, Debug)]
+pub struct HighScores(Vec<u32>);
+
+fn reset_score(mut score: ResMut<Score>) {
+    **score = 0;
+}
+
+fn add_food_points(
+    mut food_eaten: EventReader<FoodEaten>,
+    registry: Res<FoodRegistry>,
+    mut score: ResMut<Score>,
+) {
+    for event in food_eaten.read() {
+        if let Some(definition) = registry.get(&event.kind) {
+            **score += definition.points;
+        }
+    }
+}
+
+fn record_high_score(
+    score: Res<Score>,
+    mut high_scores: ResMut<HighScores>,
+    profile: Res<ActiveProfile>,
+) {
+    if **score == 0 {
+        return;
+    }
+
+    high_scores.0.push(**score);
+    high_scores.0.sort_unstable_by(|a, b| b.cmp(a));
+    high_scores.0.truncate(MAX_HIGH_SCORES);
+    storage::save(**profile, HIGH_SCORES_FILE, &*high_scores);
+}
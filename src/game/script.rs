@@ -0,0 +1,444 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedFolder, io::Reader},
+    ecs::system::SystemParam,
+    prelude::*,
+};
+use rhai::{AST, Array, CallFnOptions, Dynamic, Engine, FuncArgs, Map, Scope};
+
+use crate::{
+    game::{
+        GameMode, GameState, GameSystems, GameTick, InGame, RestartRun,
+        atlas::SpriteAtlas,
+        cat::{Cat, SegmentOf, Segments, handle_hit},
+        events::{CatCrashed, FoodEaten, RunEnded},
+        food::{Food, FoodRegistry, food},
+        grid::{Cell, CellSize},
+        level::{Level, Portal, Wall, reset_level, wall},
+        lives::Lives,
+        score::Score,
+        ticktimer::{SpeedUp, Tick},
+    },
+    theme::Theme,
+};
+
+const SCRIPTS_FOLDER: &str = "scripts";
+const MAX_OPERATIONS: u64 = 100_000;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<ScriptAsset>();
+    app.init_asset_loader::<ScriptLoader>();
+    app.init_resource::<Scripts>();
+    app.add_systems(Startup, load_scripts);
+
+    app.add_systems(
+        OnExit(GameState::None),
+        (select_script, run_spawn_hook, apply_script_actions)
+            .chain()
+            .after(GameSystems::Spawn),
+    );
+    app.add_systems(
+        RestartRun,
+        (select_script, run_spawn_hook, apply_script_actions)
+            .chain()
+            .after(reset_level),
+    );
+
+    app.add_systems(
+        GameTick,
+        (run_tick_hooks, apply_script_actions)
+            .chain()
+            .after(handle_hit)
+            .in_set(GameSystems::FixedUpdate),
+    );
+}
+
+#[derive(Asset, TypePath, Debug)]
+pub struct ScriptAsset {
+    source: String,
+}
+
+#[derive(Default)]
+struct ScriptLoader;
+
+impl AssetLoader for ScriptLoader {
+    type Asset = ScriptAsset;
+    type Settings = ();
+    type Error = io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<ScriptAsset, io::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let source = String::from_utf8(bytes)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        Ok(ScriptAsset { source })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rhai"]
+    }
+}
+
+#[derive(Resource, Debug)]
+struct ScriptFolder(Handle<LoadedFolder>);
+
+#[derive(Debug)]
+enum ScriptAction {
+    SpawnFood { cell: IVec2, kind: String },
+    RemoveFood { cell: IVec2 },
+    SpawnWall { cell: IVec2 },
+    RemoveWall { cell: IVec2 },
+    SetSpeed { factor: f32, ticks: u32 },
+    EndGame,
+    AddScore(i64),
+}
+
+struct ActiveScript {
+    name: String,
+    ast: AST,
+    state: Dynamic,
+}
+
+#[derive(Resource)]
+struct Scripts {
+    engine: Engine,
+    actions: Arc<Mutex<Vec<ScriptAction>>>,
+    active: Option<ActiveScript>,
+}
+
+fn to_cell(x: i64, y: i64) -> IVec2 {
+    IVec2::new(x as i32, y as i32)
+}
+
+impl Default for Scripts {
+    fn default() -> Self {
+        let actions = Arc::new(Mutex::new(Vec::new()));
+        let action = |build: fn(i64, i64) -> ScriptAction| {
+            let actions = Arc::clone(&actions);
+            move |x: i64, y: i64| actions.lock().unwrap().push(build(x, y))
+        };
+
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.register_fn(
+            "spawn_food",
+            action(|x, y| ScriptAction::SpawnFood {
+                cell: to_cell(x, y),
+                kind: "fish".into(),
+            }),
+        );
+        engine.register_fn("spawn_food", {
+            let actions = Arc::clone(&actions);
+            move |x: i64, y: i64, kind: &str| {
+                actions.lock().unwrap().push(ScriptAction::SpawnFood {
+                    cell: to_cell(x, y),
+                    kind: kind.into(),
+                })
+            }
+        });
+        engine.register_fn(
+            "remove_food",
+            action(|x, y| ScriptAction::RemoveFood {
+                cell: to_cell(x, y),
+            }),
+        );
+        engine.register_fn(
+            "spawn_wall",
+            action(|x, y| ScriptAction::SpawnWall {
+                cell: to_cell(x, y),
+            }),
+        );
+        engine.register_fn(
+            "remove_wall",
+            action(|x, y| ScriptAction::RemoveWall {
+                cell: to_cell(x, y),
+            }),
+        );
+        engine.register_fn("set_speed", {
+            let actions = Arc::clone(&actions);
+            move |factor: f64, ticks: i64| {
+                actions.lock().unwrap().push(ScriptAction::SetSpeed {
+                    factor: factor as f32,
+                    ticks: ticks.max(0) as u32,
+                })
+            }
+        });
+        engine.register_fn("end_game", {
+            let actions = Arc::clone(&actions);
+            move || actions.lock().unwrap().push(ScriptAction::EndGame)
+        });
+        engine.register_fn("add_score", {
+            let actions = Arc::clone(&actions);
+            move |points: i64| actions.lock().unwrap().push(ScriptAction::AddScore(points))
+        });
+
+        Self {
+            engine,
+            actions,
+            active: None,
+        }
+    }
+}
+
+impl Scripts {
+    fn call(&mut self, hook: &str, args: impl FuncArgs) {
+        let Some(active) = self.active.as_mut() else {
+            return;
+        };
+        if !active
+            .ast
+            .iter_functions()
+            .any(|function| function.name == hook)
+        {
+            return;
+        }
+
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut active.state);
+        if let Err(error) = self.engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut Scope::new(),
+            &active.ast,
+            hook,
+            args,
+        ) {
+            warn!("script {} failed in {hook}: {error}", active.name);
+        }
+    }
+}
+
+#[derive(SystemParam)]
+struct Board<'w, 's> {
+    cat: Query<'w, 's, &'static Segments, With<Cat>>,
+    cells: Query<'w, 's, &'static Cell>,
+    foods: Query<'w, 's, (&'static Cell, &'static Food)>,
+    walls: Query<'w, 's, &'static Cell, With<Wall>>,
+    level: Res<'w, Level>,
+    tick: Res<'w, Tick>,
+    score: Res<'w, Score>,
+    lives: Res<'w, Lives>,
+}
+
+fn point(cell: Vec2) -> Dynamic {
+    let mut map = Map::new();
+    map.insert("x".into(), (cell.x as i64).into());
+    map.insert("y".into(), (cell.y as i64).into());
+    map.into()
+}
+
+impl Board<'_, '_> {
+    fn snapshot(&self) -> Dynamic {
+        let cat = self
+            .cat
+            .single()
+            .map(|segments| {
+                self.cells
+                    .iter_many(segments.iter())
+                    .map(|cell| point(**cell))
+                    .collect::<Array>()
+            })
+            .unwrap_or_default();
+        let food = self
+            .foods
+            .iter()
+            .map(|(cell, food)| {
+                let mut map = point(**cell).cast::<Map>();
+                map.insert("kind".into(), food.kind.clone().into());
+                Dynamic::from(map)
+            })
+            .collect::<Array>();
+        let walls = self
+            .walls
+            .iter()
+            .map(|cell| point(**cell))
+            .collect::<Array>();
+
+        let mut board = Map::new();
+        board.insert("width".into(), i64::from(self.level.size.x).into());
+        board.insert("height".into(), i64::from(self.level.size.y).into());
+        board.insert("tick".into(), (**self.tick as i64).into());
+        board.insert("score".into(), i64::from(**self.score).into());
+        board.insert("lives".into(), i64::from(**self.lives).into());
+        board.insert("cat".into(), cat.into());
+        board.insert("food".into(), food.into());
+        board.insert("walls".into(), walls.into());
+        board.into()
+    }
+}
+
+fn load_scripts(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ScriptFolder(asset_server.load_folder(SCRIPTS_FOLDER)));
+}
+
+fn mode_script(mode: GameMode) -> &'static str {
+    match mode {
+        GameMode::Classic => "classic",
+        GameMode::Daily => "daily",
+        GameMode::Custom => "custom",
+        GameMode::Campaign => "campaign",
+    }
+}
+
+fn select_script(
+    mut scripts: ResMut<Scripts>,
+    folder: Res<ScriptFolder>,
+    folders: Res<Assets<LoadedFolder>>,
+    assets: Res<Assets<ScriptAsset>>,
+    level: Res<Level>,
+    mode: Res<GameMode>,
+) {
+    scripts.active = None;
+    scripts.actions.lock().unwrap().clear();
+
+    let name = level
+        .script
+        .clone()
+        .unwrap_or_else(|| mode_script(*mode).to_string());
+    let Some(script) = folders
+        .get(&folder.0)
+        .into_iter()
+        .flat_map(|folder| &folder.handles)
+        .find(|handle| {
+            handle
+                .path()
+                .and_then(|path| path.path().file_stem())
+                .is_some_and(|stem| *stem == *name)
+        })
+        .and_then(|handle| assets.get(&handle.clone().typed::<ScriptAsset>()))
+    else {
+        return;
+    };
+
+    match scripts.engine.compile(&script.source) {
+        Ok(ast) => {
+            info!("running script {name}");
+            scripts.active = Some(ActiveScript {
+                name,
+                ast,
+                state: Map::new().into(),
+            });
+        }
+        Err(error) => warn!("script {name} failed to compile: {error}"),
+    }
+}
+
+fn run_spawn_hook(mut scripts: ResMut<Scripts>, board: Board) {
+    scripts.call("on_spawn", (board.snapshot(),));
+}
+
+fn run_tick_hooks(
+    mut scripts: ResMut<Scripts>,
+    mut food_eaten: EventReader<FoodEaten>,
+    mut crashed: EventReader<CatCrashed>,
+    board: Board,
+) {
+    if scripts.active.is_none() {
+        food_eaten.clear();
+        crashed.clear();
+        return;
+    }
+
+    let snapshot = board.snapshot();
+    for event in food_eaten.read() {
+        scripts.call(
+            "on_food_eaten",
+            (snapshot.clone(), event.kind.clone(), point(event.cell)),
+        );
+    }
+    for event in crashed.read() {
+        scripts.call(
+            "on_collision",
+            (
+                snapshot.clone(),
+                event.cause.label().to_string(),
+                point(event.cell),
+                event.fatal,
+            ),
+        );
+    }
+    scripts.call("on_tick", (snapshot, **board.tick as i64));
+}
+
+fn apply_script_actions(
+    mut commands: Commands,
+    scripts: Res<Scripts>,
+    foods: Query<(Entity, &Cell), With<Food>>,
+    walls: Query<(Entity, &Cell), With<Wall>>,
+    occupied: Query<&Cell, Or<(With<SegmentOf>, With<Food>, With<Wall>, With<Portal>)>>,
+    (registry, atlas, size, level): (
+        Res<FoodRegistry>,
+        Res<SpriteAtlas>,
+        Res<CellSize>,
+        Res<Level>,
+    ),
+    theme: Res<Theme>,
+    mut score: ResMut<Score>,
+    mut run_ended: EventWriter<RunEnded>,
+) {
+    let actions = std::mem::take(&mut *scripts.actions.lock().unwrap());
+    let mut occupied = occupied.iter().map(|cell| **cell).collect::<Vec<_>>();
+    let free = |cell: IVec2, occupied: &[Vec2]| {
+        cell.cmpge(IVec2::ZERO).all()
+            && cell.cmplt(level.size).all()
+            && !occupied.contains(&cell.as_vec2())
+    };
+
+    for action in actions {
+        match action {
+            ScriptAction::SpawnFood { cell, kind } => {
+                let Some(definition) = registry.get(&kind) else {
+                    warn!("script tried to spawn unknown food {kind}");
+                    continue;
+                };
+                if free(cell, &occupied) {
+                    occupied.push(cell.as_vec2());
+                    commands.spawn((
+                        StateScoped(InGame::True),
+                        food(cell.as_vec2(), **size, &atlas, definition),
+                    ));
+                }
+            }
+            ScriptAction::RemoveFood { cell } => {
+                for (entity, _) in foods.iter().filter(|(_, food)| food.as_ivec2() == cell) {
+                    commands.entity(entity).despawn();
+                }
+            }
+            ScriptAction::SpawnWall { cell } => {
+                if free(cell, &occupied) {
+                    occupied.push(cell.as_vec2());
+                    commands.spawn((
+                        StateScoped(InGame::True),
+                        wall(cell.as_vec2(), **size, &theme),
+                    ));
+                }
+            }
+            ScriptAction::RemoveWall { cell } => {
+                for (entity, _) in walls.iter().filter(|(_, wall)| wall.as_ivec2() == cell) {
+                    commands.entity(entity).despawn();
+                }
+            }
+            ScriptAction::SetSpeed { factor, ticks } => {
+                if factor > 0.0 {
+                    commands.insert_resource(SpeedUp { factor, ticks });
+                }
+            }
+            ScriptAction::EndGame => {
+                run_ended.write(RunEnded);
+            }
+            ScriptAction::AddScore(points) => {
+                **score = score
+                    .saturating_add_signed(points.clamp(i32::MIN.into(), i32::MAX.into()) as i32);
+            }
+        }
+    }
+}
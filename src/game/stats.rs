@@ -0,0 +1,141 @@
+use std::{collections::HashMap, mem};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        GameMode, GameState, GameSystems, RestartRun,
+        events::{CatCrashed, CrashCause, FoodEaten, LengthChanged, TickAdvanced},
+        savegame::RunSaved,
+    },
+    profiles::{ActiveProfile, load_profile_data},
+    storage,
+};
+
+const STATS_FILE: &str = "stats.json";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LifetimeStats>();
+    app.add_systems(
+        PreUpdate,
+        load_profile_data::<LifetimeStats>(STATS_FILE).run_if(resource_changed::<ActiveProfile>),
+    );
+    app.init_resource::<RunTally>();
+
+    app.add_systems(
+        OnExit(GameState::None),
+        reset_run_tally.in_set(GameSystems::Spawn),
+    );
+
+    app.add_systems(RestartRun, reset_run_tally);
+
+    app.add_systems(
+        Update,
+        (count_play_time.run_if(in_state(GameState::Run)), tally_run)
+            .chain()
+            .in_set(GameSystems::Update),
+    );
+
+    app.add_systems(OnEnter(GameState::Over), record_lifetime_stats);
+    app.add_systems(
+        OnEnter(GameState::None),
+        record_lifetime_stats.run_if(not(resource_exists::<RunSaved>)),
+    );
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct ModeStats {
+    pub games_played: u32,
+    pub fish_eaten: u64,
+    pub tiles_travelled: u64,
+    pub longest_cat: usize,
+    pub play_time: f32,
+}
+
+impl ModeStats {
+    fn add(&mut self, run: &RunTally) {
+        self.games_played += 1;
+        self.fish_eaten += run.fish_eaten;
+        self.tiles_travelled += run.tiles_travelled;
+        self.longest_cat = self.longest_cat.max(run.longest_cat);
+        self.play_time += run.play_time;
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub total: ModeStats,
+    pub deaths: HashMap<CrashCause, u32>,
+    pub modes: HashMap<GameMode, ModeStats>,
+}
+
+impl LifetimeStats {
+    pub fn favourite_death_cause(&self) -> Option<CrashCause> {
+        self.deaths
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(cause, _)| *cause)
+    }
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize, Default, Debug)]
+pub struct RunTally {
+    pub food_eaten: u64,
+    fish_eaten: u64,
+    tiles_travelled: u64,
+    longest_cat: usize,
+    pub play_time: f32,
+    death: Option<CrashCause>,
+}
+
+fn reset_run_tally(mut tally: ResMut<RunTally>) {
+    *tally = RunTally::default();
+}
+
+fn count_play_time(time: Res<Time>, mut tally: ResMut<RunTally>) {
+    tally.play_time += time.delta_secs();
+}
+
+fn tally_run(
+    mut tally: ResMut<RunTally>,
+    mut tick_advanced: EventReader<TickAdvanced>,
+    mut food_eaten: EventReader<FoodEaten>,
+    mut length_changed: EventReader<LengthChanged>,
+    mut crashed: EventReader<CatCrashed>,
+) {
+    tally.tiles_travelled += tick_advanced.read().count() as u64;
+    for event in food_eaten.read() {
+        tally.food_eaten += 1;
+        if event.kind == "fish" {
+            tally.fish_eaten += 1;
+        }
+    }
+    for event in length_changed.read() {
+        tally.longest_cat = tally.longest_cat.max(event.length);
+    }
+    for event in crashed.read().filter(|event| event.fatal) {
+        tally.death = Some(event.cause);
+    }
+}
+
+fn record_lifetime_stats(
+    mut tally: ResMut<RunTally>,
+    mut stats: ResMut<LifetimeStats>,
+    mode: Res<GameMode>,
+    profile: Res<ActiveProfile>,
+) {
+    let run = mem::take(&mut *tally);
+    if run.tiles_travelled == 0 {
+        return;
+    }
+
+    stats.total.add(&run);
+    stats.modes.entry(*mode).or_default().add(&run);
+    if let Some(cause) = run.death {
+        *stats.deaths.entry(cause).or_default() += 1;
+    }
+    storage::save(**profile, STATS_FILE, &*stats);
+}
@@ -0,0 +1,255 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+use crate::{
+    game::{
+        GameState, GameSystems, GameTick,
+        cat::{Cat, CatHead, Direction, Segments},
+        control::InputBuffer,
+        events::{
+            CatCrashed, CrashCause, DirectionChanged, FoodEaten, LengthChanged, TickAdvanced,
+            detect_length_change,
+        },
+        grid::{Cell, cell_coords},
+        ticktimer::Tick,
+    },
+    profiles::ActiveProfile,
+    settings::Settings,
+    storage,
+};
+
+const SCHEMA_VERSION: u32 = 1;
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_FILES: usize = 20;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<TelemetryRecorder>();
+
+    app.add_systems(
+        OnExit(GameState::None),
+        start_recording.run_if(|settings: Res<Settings>| settings.telemetry),
+    );
+    app.add_systems(
+        GameTick,
+        record_events
+            .run_if(|recorder: Res<TelemetryRecorder>| recorder.file.is_some())
+            .after(detect_length_change)
+            .in_set(GameSystems::PostFixedUpdate),
+    );
+    app.add_systems(OnEnter(GameState::Over), flush_recording);
+    app.add_systems(OnEnter(GameState::Pause), flush_recording);
+    app.add_systems(OnEnter(GameState::None), stop_recording);
+}
+
+#[derive(Resource, Default)]
+struct TelemetryRecorder {
+    dir: Option<PathBuf>,
+    file: Option<BufWriter<File>>,
+    session: u64,
+    part: u32,
+    bytes: u64,
+}
+
+#[derive(Serialize)]
+struct Record<'a> {
+    version: u32,
+    session: u64,
+    tick: u64,
+    #[serde(flatten)]
+    entry: Entry<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Entry<'a> {
+    Summary {
+        length: usize,
+        head: [i32; 2],
+        input_buffer: usize,
+        frame_time: f32,
+    },
+    FoodEaten {
+        cell: [i32; 2],
+        food: &'a str,
+    },
+    Crashed {
+        cause: CrashCause,
+        cell: [i32; 2],
+        fatal: bool,
+    },
+    DirectionChanged {
+        cell: [i32; 2],
+        from: Direction,
+        to: Direction,
+    },
+    LengthChanged {
+        length: usize,
+    },
+}
+
+impl TelemetryRecorder {
+    fn open(&mut self) {
+        self.file = None;
+        self.bytes = 0;
+
+        let Some(dir) = self.dir.clone() else {
+            return;
+        };
+
+        let path = dir.join(format!("{}-{:03}.jsonl", self.session, self.part));
+        match fs::create_dir_all(&dir).and_then(|_| File::create(&path)) {
+            Ok(file) => self.file = Some(BufWriter::new(file)),
+            Err(e) => warn!("failed to open {}: {e}", path.display()),
+        }
+
+        prune_old_files(&dir);
+    }
+
+    fn write(&mut self, tick: u64, entry: Entry) {
+        let record = Record {
+            version: SCHEMA_VERSION,
+            session: self.session,
+            tick,
+            entry,
+        };
+
+        let Ok(mut line) = serde_json::to_string(&record) else {
+            return;
+        };
+        line.push('\n');
+
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+
+        if let Err(e) = file.write_all(line.as_bytes()) {
+            warn!("failed to write telemetry: {e}");
+            self.file = None;
+            return;
+        }
+
+        self.bytes += line.len() as u64;
+        if self.bytes >= MAX_FILE_BYTES {
+            self.flush();
+            self.part += 1;
+            self.open();
+        }
+    }
+
+    fn flush(&mut self) {
+        if let Some(file) = self.file.as_mut()
+            && let Err(e) = file.flush()
+        {
+            warn!("failed to flush telemetry: {e}");
+        }
+    }
+}
+
+fn prune_old_files(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut files = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "jsonl")
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+
+    for path in files.iter().rev().skip(MAX_FILES) {
+        if let Err(e) = fs::remove_file(path) {
+            warn!("failed to remove {}: {e}", path.display());
+        }
+    }
+}
+
+fn start_recording(mut recorder: ResMut<TelemetryRecorder>, profile: Res<ActiveProfile>) {
+    recorder.dir = storage::profile_dir(**profile).map(|dir| dir.join("telemetry"));
+    recorder.session = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    recorder.part = 0;
+    recorder.open();
+}
+
+fn record_events(
+    mut recorder: ResMut<TelemetryRecorder>,
+    mut tick_advanced: EventReader<TickAdvanced>,
+    mut food_eaten: EventReader<FoodEaten>,
+    mut crashed: EventReader<CatCrashed>,
+    mut direction_changed: EventReader<DirectionChanged>,
+    mut length_changed: EventReader<LengthChanged>,
+    cat: Single<&Segments, With<Cat>>,
+    head: Single<&Cell, With<CatHead>>,
+    input_buffer: Res<InputBuffer>,
+    tick: Res<Tick>,
+    time: Res<Time>,
+) {
+    for event in direction_changed.read() {
+        recorder.write(
+            **tick,
+            Entry::DirectionChanged {
+                cell: cell_coords(event.cell),
+                from: event.from,
+                to: event.to,
+            },
+        );
+    }
+    for event in food_eaten.read() {
+        recorder.write(
+            **tick,
+            Entry::FoodEaten {
+                cell: cell_coords(event.cell),
+                food: &event.kind,
+            },
+        );
+    }
+    for event in length_changed.read() {
+        recorder.write(
+            **tick,
+            Entry::LengthChanged {
+                length: event.length,
+            },
+        );
+    }
+    for event in crashed.read() {
+        recorder.write(
+            **tick,
+            Entry::Crashed {
+                cause: event.cause,
+                cell: cell_coords(event.cell),
+                fatal: event.fatal,
+            },
+        );
+    }
+    for event in tick_advanced.read() {
+        recorder.write(
+            event.tick,
+            Entry::Summary {
+                length: cat.len(),
+                head: cell_coords(***head),
+                input_buffer: input_buffer.len(),
+                frame_time: time.delta_secs(),
+            },
+        );
+    }
+}
+
+fn flush_recording(mut recorder: ResMut<TelemetryRecorder>) {
+    recorder.flush();
+}
+
+fn stop_recording(mut recorder: ResMut<TelemetryRecorder>) {
+    recorder.flush();
+    recorder.file = None;
+}
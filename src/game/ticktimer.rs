@@ -1,32 +1,194 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        GameState, GameSystems, GameTick, RestartRun,
+        events::{BoardCleared, CatCrashed, GoalReached, RunEnded, TickAdvanced},
+        level::Level,
+        powerup::{ActivePowerUps, PowerUp, SLOW_MOTION_SCALE},
+        tuning::Tuning,
+    },
+    settings::Difficulty,
+};
+
+#[cfg(feature = "dev")]
+use crate::console::{ConsoleArgs, ConsoleExt, ConsoleResult, parse_arg};
 
-use crate::game::{GameState, GameSystems};
+const MAX_FRAME_DELTA: Duration = Duration::from_millis(250);
+const MAX_TICKS_PER_FRAME: u32 = 4;
 
 pub(super) fn plugin(app: &mut App) {
-    app.insert_resource(TickTimer(Timer::from_seconds(0.1, TimerMode::Repeating)));
+    app.add_systems(Startup, clamp_frame_delta);
+
+    app.insert_resource(TickTimer(Timer::from_seconds(
+        Difficulty::default().tick_seconds(),
+        TimerMode::Repeating,
+    )));
+
+    app.add_systems(
+        Update,
+        (update_tick_duration, advance_tick_timer)
+            .chain()
+            .in_set(GameSystems::TickTimers),
+    );
+    app.add_systems(Update, run_game_ticks.in_set(GameSystems::Ticks));
+    app.add_systems(GameTick, advance_tick.in_set(GameSystems::PreFixedUpdate));
+    app.add_systems(
+        GameTick,
+        count_down_speed_up.in_set(GameSystems::FixedUpdate),
+    );
 
-    app.add_systems(Update, advance_tick_timer.in_set(GameSystems::TickTimers));
+    app.init_resource::<Tick>();
+    app.add_systems(
+        OnExit(GameState::None),
+        reset_tick.in_set(GameSystems::Spawn),
+    );
+    app.add_systems(RestartRun, reset_tick);
 
     app.add_systems(OnEnter(GameState::Pause), pause_tick_timer);
     app.add_systems(OnExit(GameState::Pause), unpause_tick_timer);
 
-    app.add_systems(OnEnter(GameState::Over), pause_tick_timer);
+    app.add_systems(
+        OnEnter(GameState::Over),
+        (reset_tick_timer, pause_tick_timer, clear_speed_up),
+    );
     app.add_systems(OnExit(GameState::Over), unpause_tick_timer);
+
+    app.add_systems(OnEnter(GameState::None), clear_speed_up);
+    app.add_systems(RestartRun, clear_speed_up);
+
+    #[cfg(feature = "dev")]
+    app.add_console_command("speed", "speed <seconds|reset>", speed_command);
 }
 
 #[derive(Resource, Deref, DerefMut)]
-struct TickTimer(Timer);
+pub struct TickTimer(Timer);
+
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+pub struct Tick(u64);
+
+#[derive(Resource, Clone, Serialize, Deserialize, Debug)]
+pub struct SpeedUp {
+    pub factor: f32,
+    pub ticks: u32,
+}
 
 pub fn tick_passed() -> impl Condition<()> {
     IntoSystem::into_system(|timer: Res<TickTimer>| timer.just_finished())
 }
 
+fn update_tick_duration(
+    mut timer: ResMut<TickTimer>,
+    speed_up: Option<Res<SpeedUp>>,
+    active_power_ups: Res<ActivePowerUps>,
+    level: Res<Level>,
+    tuning: Res<Tuning>,
+) {
+    let mut duration = tuning.tick_seconds(level.rules.difficulty)
+        / speed_up.map_or(1.0, |speed_up| speed_up.factor);
+    if active_power_ups.contains(PowerUp::SlowMotion) {
+        duration *= SLOW_MOTION_SCALE;
+    }
+    if timer.duration().as_secs_f32() != duration {
+        timer.set_duration(Duration::from_secs_f32(duration));
+    }
+}
+
+fn clamp_frame_delta(mut time: ResMut<Time<Virtual>>) {
+    time.set_max_delta(MAX_FRAME_DELTA);
+}
+
 fn advance_tick_timer(time: Res<Time>, mut timer: ResMut<TickTimer>) {
     timer.tick(time.delta());
 }
 
-fn pause_tick_timer(mut timer: ResMut<TickTimer>) {
+fn run_game_ticks(world: &mut World) -> Result {
+    let ticks = world
+        .resource::<TickTimer>()
+        .times_finished_this_tick()
+        .min(MAX_TICKS_PER_FRAME);
+    for _ in 0..ticks {
+        world.run_schedule(GameTick);
+        if world.run_system_cached(run_ended)?
+            || matches!(
+                world.resource::<NextState<GameState>>(),
+                NextState::Pending(_)
+            )
+        {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn run_ended(
+    mut crashed: EventReader<CatCrashed>,
+    mut board_cleared: EventReader<BoardCleared>,
+    mut goal_reached: EventReader<GoalReached>,
+    mut run_ended: EventReader<RunEnded>,
+) -> bool {
+    let fatal = crashed.read().any(|crash| crash.fatal);
+    let cleared = board_cleared.read().count() > 0;
+    let goal = goal_reached.read().count() > 0;
+    let ended = run_ended.read().count() > 0;
+    fatal || cleared || goal || ended
+}
+
+fn advance_tick(mut tick: ResMut<Tick>, mut tick_advanced: EventWriter<TickAdvanced>) {
+    tick.0 += 1;
+    tick_advanced.write(TickAdvanced { tick: tick.0 });
+}
+
+fn reset_tick(mut tick: ResMut<Tick>) {
+    tick.0 = 0;
+}
+
+fn count_down_speed_up(mut commands: Commands, speed_up: Option<ResMut<SpeedUp>>) {
+    let Some(mut speed_up) = speed_up else {
+        return;
+    };
+
+    speed_up.ticks = speed_up.ticks.saturating_sub(1);
+    if speed_up.ticks == 0 {
+        commands.remove_resource::<SpeedUp>();
+    }
+}
+
+fn clear_speed_up(mut commands: Commands) {
+    commands.remove_resource::<SpeedUp>();
+}
+
+#[cfg(feature = "dev")]
+fn speed_command(
+    In(args): In<ConsoleArgs>,
+    mut commands: Commands,
+    level: Res<Level>,
+    tuning: Res<Tuning>,
+) -> ConsoleResult {
+    if args.first().is_some_and(|arg| arg == "reset") {
+        commands.remove_resource::<SpeedUp>();
+        return Ok("tick interval reset".into());
+    }
+
+    let seconds = parse_arg::<f32>(&args, 0, "seconds")?;
+    if seconds <= 0.0 {
+        return Err("tick interval must be positive".into());
+    }
+    commands.insert_resource(SpeedUp {
+        factor: tuning.tick_seconds(level.rules.difficulty) / seconds,
+        ticks: u32::MAX,
+    });
+    Ok(format!("tick interval set to {seconds}s"))
+}
+
+fn reset_tick_timer(mut timer: ResMut<TickTimer>) {
     timer.reset();
+}
+
+fn pause_tick_timer(mut timer: ResMut<TickTimer>) {
     timer.pause();
 }
 
@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{
+        food::FoodDefinition,
+        powerup::{PowerUp, PowerUpRule},
+    },
+    settings::Difficulty,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Tuning>();
+}
+
+#[derive(Resource, Asset, TypePath, Clone, Default, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct Tuning {
+    pub tick_seconds: HashMap<Difficulty, f32>,
+    pub food_weights: HashMap<String, u32>,
+    pub power_ups: HashMap<PowerUp, PowerUpRule>,
+}
+
+impl Tuning {
+    pub fn tick_seconds(&self, difficulty: Difficulty) -> f32 {
+        self.tick_seconds
+            .get(&difficulty)
+            .copied()
+            .unwrap_or(difficulty.tick_seconds())
+    }
+
+    pub fn food_weight(&self, food: &FoodDefinition) -> u32 {
+        self.food_weights
+            .get(&food.name)
+            .copied()
+            .unwrap_or(food.weight)
+    }
+
+    pub fn power_up_rule(&self, power_up: PowerUp) -> PowerUpRule {
+        self.power_ups
+            .get(&power_up)
+            .copied()
+            .unwrap_or(power_up.default_rule())
+    }
+}
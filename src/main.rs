@@ -1,18 +1,35 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::too_many_arguments)]
 
+#[cfg(feature = "dev")]
+mod console;
 mod game;
 mod menus;
+mod mods;
+mod profiles;
 mod screens;
+mod settings;
+mod storage;
+mod theme;
 mod ui;
 
 use bevy::prelude::*;
 
 fn main() {
     let mut app = App::new();
+    mods::register_asset_source(&mut app);
     app.add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()));
-    app.add_plugins((menus::plugin, screens::plugin, game::plugin));
-    app.insert_resource(ClearColor(Color::BLACK));
+    app.add_plugins((
+        profiles::plugin,
+        settings::plugin,
+        menus::plugin,
+        screens::plugin,
+        game::plugin,
+        theme::plugin,
+        mods::plugin,
+    ));
+    #[cfg(feature = "dev")]
+    app.add_plugins(console::plugin);
     app.add_systems(Startup, setup_camera);
     app.run();
 }
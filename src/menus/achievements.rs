@@ -0,0 +1,111 @@
+use bevy::{
+    ecs::spawn::SpawnWith,
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use crate::{
+    game::achievements::{AchievementProgress, AchievementRegistry},
+    menus::Menu,
+    ui::button,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Achievements), spawn_achievements_menu);
+}
+
+fn spawn_achievements_menu(
+    mut commands: Commands,
+    mut dir_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    registry: Res<AchievementRegistry>,
+    progress: Res<AchievementProgress>,
+) {
+    let rows = registry
+        .achievements
+        .iter()
+        .map(|achievement| {
+            let target = achievement.condition.target();
+            let unlocked = progress.unlocked.contains(&achievement.id);
+            let status = if unlocked {
+                "Unlocked".to_string()
+            } else {
+                let best = progress.best.get(&achievement.id).copied().unwrap_or(0);
+                format!("{}/{target}", best.min(target))
+            };
+            let color = if unlocked {
+                Color::srgb_u8(0xe0, 0xc0, 0x40)
+            } else {
+                Color::srgb_u8(0xa0, 0xa0, 0xa0)
+            };
+
+            commands
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        margin: UiRect::bottom(Val::Px(10.0)),
+                        ..default()
+                    },
+                    children![
+                        (
+                            Text::new(format!("{}  [{status}]", achievement.name)),
+                            TextFont::from_font_size(30.0),
+                            TextColor(color),
+                        ),
+                        (
+                            Text::new(achievement.description.clone()),
+                            TextFont::from_font_size(20.0),
+                            TextColor(Color::srgb_u8(0x80, 0x80, 0x80)),
+                        ),
+                    ],
+                ))
+                .id()
+        })
+        .collect::<Vec<_>>();
+
+    let entries = [commands
+        .spawn(button("Back"))
+        .observe(
+            |_: Trigger<FocusedInput<KeyboardInput>>,
+             keyboard_input: Res<ButtonInput<KeyCode>>,
+             mut next_menu: ResMut<NextState<Menu>>| {
+                if keyboard_input.just_pressed(KeyCode::Enter) {
+                    next_menu.set(Menu::Main);
+                }
+            },
+        )
+        .id()];
+
+    dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
+    input_focus.set(entries[0]);
+
+    commands.spawn((
+        StateScoped(Menu::Achievements),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                })
+                .add_children(&rows);
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                })
+                .add_children(&entries);
+        })),
+    ));
+}
@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+
+use bevy::{
+    ecs::spawn::SpawnWith,
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use crate::{
+    game::{
+        grid::{
+            CELL_DEPTH, Cell, CellSize, board_cell, cell_coords, move_camera_to_grid,
+            setup_cell_size,
+        },
+        history::{RunHistory, RunRecord},
+        level::{Board, Level},
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::Theme,
+    ui::button,
+};
+
+const HEATMAP_DEPTH: f32 = 10.0;
+const MAX_BARS: usize = 60;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<AnalyticsView>();
+
+    app.add_systems(
+        OnEnter(Menu::Analytics),
+        (setup_cell_size, move_camera_to_grid, spawn_analytics_menu).chain(),
+    );
+    app.add_systems(OnExit(Menu::Analytics), restore_board);
+    app.add_systems(
+        Update,
+        (update_analytics_labels, paint_heatmap, draw_length_chart)
+            .run_if(in_state(Menu::Analytics).and(resource_changed::<AnalyticsView>)),
+    );
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+enum HeatLayer {
+    #[default]
+    Deaths,
+    FoodSpawns,
+    Turns,
+}
+
+impl HeatLayer {
+    fn label(self) -> &'static str {
+        match self {
+            HeatLayer::Deaths => "Deaths",
+            HeatLayer::FoodSpawns => "Food",
+            HeatLayer::Turns => "Turns",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            HeatLayer::Deaths => HeatLayer::FoodSpawns,
+            HeatLayer::FoodSpawns => HeatLayer::Turns,
+            HeatLayer::Turns => HeatLayer::Deaths,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            HeatLayer::Deaths => Color::srgb_u8(0xe0, 0x30, 0x30),
+            HeatLayer::FoodSpawns => Color::srgb_u8(0x30, 0xc0, 0x50),
+            HeatLayer::Turns => Color::srgb_u8(0x40, 0x80, 0xe0),
+        }
+    }
+
+    fn cells(self, run: &RunRecord) -> &[[i32; 2]] {
+        match self {
+            HeatLayer::Deaths => &run.deaths,
+            HeatLayer::FoodSpawns => &run.food_spawns,
+            HeatLayer::Turns => &run.turns,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+enum Scope {
+    #[default]
+    LastRun,
+    AllRuns,
+}
+
+impl Scope {
+    fn label(self) -> &'static str {
+        match self {
+            Scope::LastRun => "Last Run",
+            Scope::AllRuns => "All Runs",
+        }
+    }
+
+    fn runs(self, history: &RunHistory) -> &[RunRecord] {
+        match self {
+            Scope::LastRun => {
+                let start = history.runs.len().saturating_sub(1);
+                &history.runs[start..]
+            }
+            Scope::AllRuns => &history.runs,
+        }
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+struct AnalyticsView {
+    layer: HeatLayer,
+    scope: Scope,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+enum AnalyticsEntry {
+    Layer,
+    Scope,
+}
+
+impl AnalyticsEntry {
+    fn label(self, view: &AnalyticsView) -> String {
+        match self {
+            AnalyticsEntry::Layer => format!("Layer: {}", view.layer.label()),
+            AnalyticsEntry::Scope => format!("Scope: {}", view.scope.label()),
+        }
+    }
+
+    fn toggle(self, view: &mut AnalyticsView) {
+        match self {
+            AnalyticsEntry::Layer => view.layer = view.layer.next(),
+            AnalyticsEntry::Scope => {
+                view.scope = match view.scope {
+                    Scope::LastRun => Scope::AllRuns,
+                    Scope::AllRuns => Scope::LastRun,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+struct LengthChart;
+
+#[derive(Component, Debug)]
+struct AnalyticsSummary;
+
+fn spawn_analytics_menu(
+    mut commands: Commands,
+    mut dir_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    mut view: ResMut<AnalyticsView>,
+    size: Res<CellSize>,
+    level: Res<Level>,
+    theme: Res<Theme>,
+    board: Query<(), With<Board>>,
+) {
+    if board.is_empty() {
+        for y in 0..level.size.y {
+            for x in 0..level.size.x {
+                commands.spawn((
+                    StateScoped(Menu::Analytics),
+                    Board,
+                    board_cell(x, y, **size, &theme),
+                ));
+            }
+        }
+    }
+
+    let mut entries = [AnalyticsEntry::Layer, AnalyticsEntry::Scope]
+        .map(|entry| {
+            commands
+                .spawn((entry, button(&entry.label(&view))))
+                .observe(
+                    move |_: Trigger<FocusedInput<KeyboardInput>>,
+                          keyboard_input: Res<ButtonInput<KeyCode>>,
+                          mut view: ResMut<AnalyticsView>| {
+                        if keyboard_input.just_pressed(KeyCode::Enter) {
+                            entry.toggle(&mut view);
+                        }
+                    },
+                )
+                .id()
+        })
+        .to_vec();
+
+    entries.push(
+        commands
+            .spawn(button("Back"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 screen: Res<State<Screen>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(previous_menu(*screen.get()));
+                    }
+                },
+            )
+            .id(),
+    );
+
+    dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
+    input_focus.set(entries[0]);
+
+    commands.spawn((
+        StateScoped(Menu::Analytics),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::FlexEnd,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![(
+            Node {
+                width: Val::Percent(100.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+            Children::spawn((
+                Spawn((
+                    AnalyticsSummary,
+                    Text::default(),
+                    TextFont::from_font_size(30.0),
+                    TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+                )),
+                Spawn((
+                    LengthChart,
+                    Node {
+                        width: Val::Percent(80.0),
+                        height: Val::Px(100.0),
+                        margin: UiRect::vertical(Val::Px(10.0)),
+                        align_items: AlignItems::FlexEnd,
+                        ..default()
+                    },
+                )),
+                SpawnWith(move |parent: &mut ChildSpawner| {
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            ..default()
+                        })
+                        .add_children(&entries);
+                }),
+            )),
+        )],
+    ));
+
+    view.set_changed();
+}
+
+fn previous_menu(screen: Screen) -> Menu {
+    match screen {
+        Screen::Over => Menu::Over,
+        _ => Menu::Main,
+    }
+}
+
+fn update_analytics_labels(
+    entries: Query<(&AnalyticsEntry, &Children)>,
+    mut text: Query<&mut Text>,
+    view: Res<AnalyticsView>,
+) {
+    for (entry, children) in &entries {
+        if let Ok(mut text) = text.get_mut(children[0]) {
+            text.0 = entry.label(&view);
+        }
+    }
+}
+
+fn paint_heatmap(
+    mut cells: Query<(&Cell, &mut Sprite, &mut Transform), With<Board>>,
+    view: Res<AnalyticsView>,
+    history: Res<RunHistory>,
+    theme: Res<Theme>,
+) {
+    let mut counts = HashMap::<[i32; 2], usize>::new();
+    for run in view.scope.runs(&history) {
+        for coords in view.layer.cells(run) {
+            *counts.entry(*coords).or_default() += 1;
+        }
+    }
+    let max = counts.values().copied().max().unwrap_or(1) as f32;
+
+    for (cell, mut sprite, mut transform) in &mut cells {
+        let coords = cell_coords(**cell);
+        let base = theme.board_color(coords[0], coords[1]);
+        sprite.color = match counts.get(&coords) {
+            Some(&count) => base.mix(&view.layer.color(), 0.25 + 0.75 * count as f32 / max),
+            None => base,
+        };
+        transform.translation.z = HEATMAP_DEPTH;
+    }
+}
+
+fn restore_board(
+    mut cells: Query<(&Cell, &mut Sprite, &mut Transform), With<Board>>,
+    theme: Res<Theme>,
+) {
+    for (cell, mut sprite, mut transform) in &mut cells {
+        let coords = cell_coords(**cell);
+        sprite.color = theme.board_color(coords[0], coords[1]);
+        transform.translation.z = CELL_DEPTH;
+    }
+}
+
+fn draw_length_chart(
+    mut commands: Commands,
+    chart: Single<Entity, With<LengthChart>>,
+    mut summary: Single<&mut Text, With<AnalyticsSummary>>,
+    view: Res<AnalyticsView>,
+    history: Res<RunHistory>,
+) {
+    let runs = view.scope.runs(&history);
+    let (title, lengths) = match view.scope {
+        Scope::LastRun => (
+            "Length over time",
+            runs.last()
+                .map(|run| run.lengths.clone())
+                .unwrap_or_default(),
+        ),
+        Scope::AllRuns => (
+            "Peak length per run",
+            runs.iter().map(RunRecord::peak_length).collect(),
+        ),
+    };
+
+    summary.0 = if runs.is_empty() {
+        "No runs recorded".to_string()
+    } else {
+        let count = runs
+            .iter()
+            .map(|run| view.layer.cells(run).len())
+            .sum::<usize>();
+        format!(
+            "{title}  |  Runs: {}  {}: {count}",
+            runs.len(),
+            view.layer.label()
+        )
+    };
+
+    let bars = downsample(&lengths, MAX_BARS);
+    let max = bars.iter().copied().max().unwrap_or(1).max(1) as f32;
+    let width = 100.0 / bars.len().max(1) as f32;
+
+    commands
+        .entity(*chart)
+        .despawn_related::<Children>()
+        .with_children(|parent| {
+            for length in bars {
+                parent.spawn((
+                    Node {
+                        width: Val::Percent(width),
+                        height: Val::Percent(length as f32 / max * 100.0),
+                        margin: UiRect::horizontal(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BackgroundColor(view.layer.color()),
+                ));
+            }
+        });
+}
+
+fn downsample(values: &[usize], max: usize) -> Vec<usize> {
+    if values.len() <= max {
+        return values.to_vec();
+    }
+    values
+        .chunks(values.len().div_ceil(max))
+        .map(|chunk| chunk.iter().copied().max().unwrap_or_default())
+        .collect()
+}
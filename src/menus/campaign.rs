@@ -0,0 +1,108 @@
+use bevy::{
+    ecs::spawn::SpawnWith,
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use crate::{
+    game::{
+        GameMode,
+        campaign::{Campaign, CampaignProgress, CampaignStage, stars_label},
+    },
+    menus::Menu,
+    screens::Screen,
+    ui::button,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Campaign), spawn_campaign_menu);
+}
+
+fn spawn_campaign_menu(
+    mut commands: Commands,
+    mut dir_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    campaign: Res<Campaign>,
+    progress: Res<CampaignProgress>,
+) {
+    let mut entries = campaign
+        .levels
+        .iter()
+        .enumerate()
+        .map(|(index, level)| {
+            if !progress.unlocked(&campaign, index) {
+                return commands
+                    .spawn(button(&format!("{}. Locked", index + 1)))
+                    .id();
+            }
+
+            commands
+                .spawn(button(&format!(
+                    "{}. {} {}  {}",
+                    index + 1,
+                    level.name,
+                    stars_label(progress.stars(level)),
+                    level.goal.label()
+                )))
+                .observe(
+                    move |_: Trigger<FocusedInput<KeyboardInput>>,
+                          keyboard_input: Res<ButtonInput<KeyCode>>,
+                          mut mode: ResMut<GameMode>,
+                          mut stage: ResMut<CampaignStage>,
+                          mut next_screen: ResMut<NextState<Screen>>| {
+                        if keyboard_input.just_pressed(KeyCode::Enter) {
+                            *mode = GameMode::Campaign;
+                            **stage = index;
+                            next_screen.set(Screen::Gameplay);
+                        }
+                    },
+                )
+                .id()
+        })
+        .collect::<Vec<_>>();
+
+    entries.push(
+        commands
+            .spawn(button("Back"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 screen: Res<State<Screen>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(match screen.get() {
+                            Screen::Over => Menu::Over,
+                            _ => Menu::Main,
+                        });
+                    }
+                },
+            )
+            .id(),
+    );
+
+    dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
+    input_focus.set(entries[0]);
+
+    commands.spawn((
+        StateScoped(Menu::Campaign),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                })
+                .add_children(&entries);
+        })),
+    ));
+}
@@ -0,0 +1,179 @@
+use bevy::{
+    ecs::spawn::SpawnWith,
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use crate::{
+    menus::Menu,
+    settings::{KeyBindings, Settings},
+    ui::button,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Rebinding>();
+    app.add_systems(OnEnter(Menu::Controls), spawn_controls_menu);
+    app.add_systems(OnExit(Menu::Controls), stop_rebinding);
+    app.add_systems(
+        Update,
+        (
+            rebind_key.run_if(|rebinding: Res<Rebinding>| rebinding.is_some()),
+            update_controls_labels
+                .run_if(resource_changed::<Settings>.or(resource_changed::<Rebinding>)),
+        )
+            .chain()
+            .run_if(in_state(Menu::Controls)),
+    );
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+enum ControlsEntry {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl ControlsEntry {
+    fn label(self, bindings: &KeyBindings, rebinding: bool) -> String {
+        let name = match self {
+            ControlsEntry::Up => "Up",
+            ControlsEntry::Down => "Down",
+            ControlsEntry::Left => "Left",
+            ControlsEntry::Right => "Right",
+        };
+        if rebinding {
+            format!("{name}: ...")
+        } else {
+            format!("{name}: {:?}", self.key(bindings))
+        }
+    }
+
+    fn key(self, bindings: &KeyBindings) -> KeyCode {
+        match self {
+            ControlsEntry::Up => bindings.up,
+            ControlsEntry::Down => bindings.down,
+            ControlsEntry::Left => bindings.left,
+            ControlsEntry::Right => bindings.right,
+        }
+    }
+
+    fn key_mut(self, bindings: &mut KeyBindings) -> &mut KeyCode {
+        match self {
+            ControlsEntry::Up => &mut bindings.up,
+            ControlsEntry::Down => &mut bindings.down,
+            ControlsEntry::Left => &mut bindings.left,
+            ControlsEntry::Right => &mut bindings.right,
+        }
+    }
+}
+
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+struct Rebinding(Option<ControlsEntry>);
+
+fn spawn_controls_menu(
+    mut commands: Commands,
+    mut dir_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    settings: Res<Settings>,
+) {
+    let mut entries = [
+        ControlsEntry::Up,
+        ControlsEntry::Down,
+        ControlsEntry::Left,
+        ControlsEntry::Right,
+    ]
+    .map(|entry| {
+        commands
+            .spawn((entry, button(&entry.label(&settings.key_bindings, false))))
+            .observe(
+                move |_: Trigger<FocusedInput<KeyboardInput>>,
+                      keyboard_input: Res<ButtonInput<KeyCode>>,
+                      mut rebinding: ResMut<Rebinding>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        **rebinding = Some(entry);
+                    }
+                },
+            )
+            .id()
+    })
+    .to_vec();
+
+    entries.push(
+        commands
+            .spawn(button("Back"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Settings);
+                    }
+                },
+            )
+            .id(),
+    );
+
+    dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
+    input_focus.set(entries[0]);
+
+    commands.spawn((
+        StateScoped(Menu::Controls),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                })
+                .add_children(&entries);
+        })),
+    ));
+}
+
+fn rebind_key(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut settings: ResMut<Settings>,
+) {
+    let Some(entry) = **rebinding else {
+        return;
+    };
+
+    let Some(key) = keyboard_input
+        .get_just_pressed()
+        .find(|key| !matches!(key, KeyCode::Enter | KeyCode::Escape))
+    else {
+        return;
+    };
+
+    *entry.key_mut(&mut settings.key_bindings) = *key;
+    **rebinding = None;
+}
+
+fn stop_rebinding(mut rebinding: ResMut<Rebinding>) {
+    **rebinding = None;
+}
+
+fn update_controls_labels(
+    entries: Query<(&ControlsEntry, &Children)>,
+    mut text: Query<&mut Text>,
+    settings: Res<Settings>,
+    rebinding: Res<Rebinding>,
+) {
+    for (entry, children) in &entries {
+        if let Ok(mut text) = text.get_mut(children[0]) {
+            text.0 = entry.label(&settings.key_bindings, **rebinding == Some(*entry));
+        }
+    }
+}
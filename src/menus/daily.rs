@@ -0,0 +1,202 @@
+use bevy::{
+    ecs::spawn::SpawnWith,
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use crate::{
+    game::{
+        GameMode,
+        daily::{DailyChallenge, DailyHistory},
+    },
+    menus::Menu,
+    profiles::ActiveProfile,
+    screens::Screen,
+    storage,
+    ui::button,
+};
+
+const RECENT_RESULTS: usize = 5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Daily), spawn_daily_menu);
+}
+
+#[derive(Component, Debug)]
+struct ExportStatus;
+
+fn daily_line(text: String, font_size: f32) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont::from_font_size(font_size),
+        TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+    )
+}
+
+fn spawn_daily_menu(
+    mut commands: Commands,
+    mut dir_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    history: Res<DailyHistory>,
+) {
+    let today = DailyChallenge::today();
+    let level = today.level();
+    let result = history.result(today);
+
+    let mut lines = vec![
+        daily_line(format!("Daily Challenge {}", today.date()), 40.0),
+        daily_line(
+            format!(
+                "{}x{} board, {} walls, {}, lives {}, food {}",
+                level.size.x,
+                level.size.y,
+                level.walls.len(),
+                level.rules.difficulty.label(),
+                if level.rules.lives { "on" } else { "off" },
+                level.rules.food_count,
+            ),
+            20.0,
+        ),
+        daily_line(
+            match result {
+                Some(result) => format!(
+                    "Today: score {}, length {}  ({})",
+                    result.score,
+                    result.length,
+                    result.code()
+                ),
+                None => "Today: not played".to_string(),
+            },
+            30.0,
+        ),
+        daily_line(format!("Streak: {} days", history.streak(today)), 30.0),
+    ];
+    lines.extend(
+        history
+            .results
+            .iter()
+            .rev()
+            .filter(|result| result.day != today.day)
+            .take(RECENT_RESULTS)
+            .map(|result| {
+                daily_line(
+                    format!(
+                        "{}: score {}, length {}",
+                        DailyChallenge { day: result.day }.date(),
+                        result.score,
+                        result.length
+                    ),
+                    20.0,
+                )
+            }),
+    );
+    let lines = lines
+        .into_iter()
+        .map(|line| commands.spawn(line).id())
+        .collect::<Vec<_>>();
+    let status = commands
+        .spawn((ExportStatus, daily_line(String::new(), 20.0)))
+        .id();
+
+    let mut entries = vec![
+        commands
+            .spawn(button(if result.is_some() { "Practice" } else { "Play" }))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut mode: ResMut<GameMode>,
+                 mut next_screen: ResMut<NextState<Screen>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        *mode = GameMode::Daily;
+                        next_screen.set(Screen::Gameplay);
+                    }
+                },
+            )
+            .id(),
+    ];
+
+    if result.is_some() {
+        entries.push(
+            commands
+                .spawn(button("Export"))
+                .observe(
+                    |_: Trigger<FocusedInput<KeyboardInput>>,
+                     keyboard_input: Res<ButtonInput<KeyCode>>,
+                     history: Res<DailyHistory>,
+                     profile: Res<ActiveProfile>,
+                     mut status: Single<&mut Text, With<ExportStatus>>| {
+                        if !keyboard_input.just_pressed(KeyCode::Enter) {
+                            return;
+                        }
+
+                        let today = DailyChallenge::today();
+                        let Some(result) = history.result(today) else {
+                            return;
+                        };
+
+                        let code = result.code();
+                        info!("daily result: {code}");
+                        status.0 = match storage::save_text(
+                            **profile,
+                            &format!("daily-{}.txt", today.date()),
+                            &code,
+                        ) {
+                            Some(path) => format!("Exported to {}", path.display()),
+                            None => "Export failed".to_string(),
+                        };
+                    },
+                )
+                .id(),
+        );
+    }
+
+    entries.push(
+        commands
+            .spawn(button("Back"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Main);
+                    }
+                },
+            )
+            .id(),
+    );
+
+    dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
+    input_focus.set(entries[0]);
+
+    commands.spawn((
+        StateScoped(Menu::Daily),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                })
+                .add_children(&lines)
+                .add_child(status);
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                })
+                .add_children(&entries);
+        })),
+    ));
+}
@@ -6,7 +6,20 @@ use bevy::{
     prelude::*,
 };
 
-use crate::{menus::Menu, screens::Screen, ui::button};
+use crate::{
+    game::{
+        GameMode,
+        campaign::CampaignStage,
+        level::{CUSTOM_LEVEL_FILE, Level, LoadedLevels},
+        savegame::{PendingResume, discard_save, load_save},
+    },
+    menus::Menu,
+    profiles::{ActiveProfile, Profiles},
+    screens::Screen,
+    settings::Settings,
+    storage,
+    ui::button,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
@@ -17,20 +30,163 @@ fn spawn_main_menu(
     mut dir_nav_map: ResMut<DirectionalNavigationMap>,
     mut input_focus: ResMut<InputFocus>,
     asset_server: Res<AssetServer>,
+    profiles: Res<Profiles>,
+    active_profile: Res<ActiveProfile>,
 ) {
-    let entries = [
+    let profile_name = active_profile
+        .and_then(|id| profiles.name(id))
+        .unwrap_or_default();
+
+    let mut entries = Vec::new();
+    if load_save(**active_profile).is_some() {
+        entries.push(
+            commands
+                .spawn(button("Resume"))
+                .observe(
+                    |_: Trigger<FocusedInput<KeyboardInput>>,
+                     keyboard_input: Res<ButtonInput<KeyCode>>,
+                     mut commands: Commands,
+                     mut mode: ResMut<GameMode>,
+                     mut stage: ResMut<CampaignStage>,
+                     profile: Res<ActiveProfile>,
+                     mut next_screen: ResMut<NextState<Screen>>| {
+                        if !keyboard_input.just_pressed(KeyCode::Enter) {
+                            return;
+                        }
+
+                        let Some(save) = load_save(**profile) else {
+                            return;
+                        };
+                        discard_save(**profile);
+                        *mode = save.mode;
+                        **stage = save.campaign_stage;
+                        commands.insert_resource(PendingResume(save));
+                        next_screen.set(Screen::Gameplay);
+                    },
+                )
+                .id(),
+        );
+    }
+
+    entries.extend([
         commands
             .spawn(button("Start"))
             .observe(
                 |_: Trigger<FocusedInput<KeyboardInput>>,
                  keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut mode: ResMut<GameMode>,
                  mut next_screen: ResMut<NextState<Screen>>| {
                     if keyboard_input.just_pressed(KeyCode::Enter) {
+                        *mode = GameMode::Classic;
                         next_screen.set(Screen::Gameplay);
                     }
                 },
             )
             .id(),
+        commands
+            .spawn(button("Campaign"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Campaign);
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Daily"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Daily);
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Editor"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut mode: ResMut<GameMode>,
+                 mut level: ResMut<Level>,
+                 levels: Res<LoadedLevels>,
+                 settings: Res<Settings>,
+                 profile: Res<ActiveProfile>,
+                 mut next_screen: ResMut<NextState<Screen>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        *mode = GameMode::Custom;
+                        *level = storage::load(**profile, CUSTOM_LEVEL_FILE)
+                            .unwrap_or_else(|| levels.classic(&settings));
+                        next_screen.set(Screen::Editor);
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Settings"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Settings);
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Achievements"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Achievements);
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Stats"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Stats);
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Analytics"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Analytics);
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button(&format!("Profile: {profile_name}")))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Profile);
+                    }
+                },
+            )
+            .id(),
         commands
             .spawn(button("Quit"))
             .observe(
@@ -43,7 +199,7 @@ fn spawn_main_menu(
                 },
             )
             .id(),
-    ];
+    ]);
 
     dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
     input_focus.set(entries[0]);
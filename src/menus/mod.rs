@@ -1,13 +1,36 @@
+mod achievements;
+mod analytics;
+mod campaign;
+mod controls;
+mod daily;
 mod main;
+mod mods;
 mod navigate;
 mod over;
 mod pause;
+mod profiles;
+mod settings;
+mod stats;
 
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
-    app.add_plugins((navigate::plugin, main::plugin, pause::plugin, over::plugin));
+    app.add_plugins((
+        navigate::plugin,
+        main::plugin,
+        pause::plugin,
+        over::plugin,
+        settings::plugin,
+        analytics::plugin,
+        achievements::plugin,
+        stats::plugin,
+        controls::plugin,
+        profiles::plugin,
+        daily::plugin,
+        campaign::plugin,
+        mods::plugin,
+    ));
 }
 
 #[derive(States, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
@@ -18,4 +41,15 @@ pub enum Menu {
     Main,
     Pause,
     Over,
+    Settings,
+    Analytics,
+    Achievements,
+    Stats,
+    Controls,
+    Profiles,
+    Profile,
+    ProfileName,
+    Daily,
+    Campaign,
+    Mods,
 }
@@ -0,0 +1,142 @@
+use bevy::{
+    ecs::spawn::SpawnWith,
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use crate::{menus::Menu, mods::Mods, ui::button};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Mods), spawn_mods_menu);
+    app.add_systems(
+        Update,
+        update_mods_labels.run_if(in_state(Menu::Mods).and(resource_changed::<Mods>)),
+    );
+}
+
+#[derive(Component, Clone, Debug)]
+struct ModEntry(String);
+
+#[derive(Component, Debug)]
+struct ModNotes;
+
+fn mod_label(mods: &Mods, name: &str) -> String {
+    let version = mods
+        .mods
+        .iter()
+        .find(|info| info.manifest.name == name)
+        .map(|info| info.manifest.version.as_str())
+        .unwrap_or_default();
+    let state = if mods.enabled(name) { "On" } else { "Off" };
+    format!("{name} v{version}: {state}")
+}
+
+fn mod_notes(mods: &Mods) -> String {
+    let mut notes = mods.problems.clone();
+    notes.extend(mods.conflicts());
+    if mods.mods.is_empty() {
+        notes.push(match Mods::dir() {
+            Some(dir) => format!("No mods found in {}", dir.display()),
+            None => "No mods found".to_string(),
+        });
+    }
+    if mods.needs_restart() {
+        notes.push("Changes apply after restart".to_string());
+    }
+    notes.join("\n")
+}
+
+fn spawn_mods_menu(
+    mut commands: Commands,
+    mut dir_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    mods: Res<Mods>,
+) {
+    let mut entries = mods
+        .mods
+        .iter()
+        .map(|info| {
+            let entry = ModEntry(info.manifest.name.clone());
+            commands
+                .spawn((entry.clone(), button(&mod_label(&mods, &entry.0))))
+                .observe(
+                    move |_: Trigger<FocusedInput<KeyboardInput>>,
+                          keyboard_input: Res<ButtonInput<KeyCode>>,
+                          mut mods: ResMut<Mods>| {
+                        if keyboard_input.just_pressed(KeyCode::Enter) {
+                            mods.toggle(&entry.0);
+                        }
+                    },
+                )
+                .id()
+        })
+        .collect::<Vec<_>>();
+
+    entries.push(
+        commands
+            .spawn(button("Back"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Settings);
+                    }
+                },
+            )
+            .id(),
+    );
+
+    dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
+    input_focus.set(entries[0]);
+
+    let notes = mod_notes(&mods);
+
+    commands.spawn((
+        StateScoped(Menu::Mods),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                })
+                .add_children(&entries);
+            parent.spawn((
+                ModNotes,
+                Node {
+                    margin: UiRect::top(Val::Px(20.0)),
+                    max_width: Val::Percent(80.0),
+                    ..default()
+                },
+                Text::new(notes),
+                TextFont::from_font_size(18.0),
+                TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+            ));
+        })),
+    ));
+}
+
+fn update_mods_labels(
+    entries: Query<(&ModEntry, &Children)>,
+    mut text: Query<&mut Text, Without<ModNotes>>,
+    mut notes: Single<&mut Text, With<ModNotes>>,
+    mods: Res<Mods>,
+) {
+    for (entry, children) in &entries {
+        if let Ok(mut text) = text.get_mut(children[0]) {
+            text.0 = mod_label(&mods, &entry.0);
+        }
+    }
+    notes.0 = mod_notes(&mods);
+}
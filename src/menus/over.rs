@@ -6,7 +6,15 @@ use bevy::{
     prelude::*,
 };
 
-use crate::{menus::Menu, screens::Screen, ui::button};
+use crate::{
+    game::{
+        GameMode,
+        campaign::{Campaign, CampaignRun, CampaignStage, stars_label},
+    },
+    menus::Menu,
+    screens::Screen,
+    ui::button,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Over), spawn_pause_menu);
@@ -16,16 +24,88 @@ fn spawn_pause_menu(
     mut commands: Commands,
     mut dir_nav_map: ResMut<DirectionalNavigationMap>,
     mut input_focus: ResMut<InputFocus>,
+    mode: Res<GameMode>,
+    campaign: Res<Campaign>,
+    stage: Res<CampaignStage>,
+    run: Res<CampaignRun>,
 ) {
-    let entries = [
+    let in_campaign = *mode == GameMode::Campaign;
+    let mut entries = Vec::new();
+    let mut lines = Vec::new();
+
+    if in_campaign {
+        lines.push(
+            commands
+                .spawn((
+                    Text::new(match run.stars {
+                        Some(stars) => format!("Level complete! {}", stars_label(stars)),
+                        None => "Level failed".to_string(),
+                    }),
+                    TextFont::from_font_size(40.0),
+                    TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+                ))
+                .id(),
+        );
+
+        if run.stars.is_some() && campaign.level(**stage + 1).is_some() {
+            entries.push(
+                commands
+                    .spawn(button("Next Level"))
+                    .observe(
+                        |_: Trigger<FocusedInput<KeyboardInput>>,
+                         keyboard_input: Res<ButtonInput<KeyCode>>,
+                         mut stage: ResMut<CampaignStage>,
+                         mut next_screen: ResMut<NextState<Screen>>| {
+                            if keyboard_input.just_pressed(KeyCode::Enter) {
+                                **stage += 1;
+                                next_screen.set(Screen::Gameplay);
+                            }
+                        },
+                    )
+                    .id(),
+            );
+        }
+    }
+
+    entries.extend([commands
+        .spawn(button(if in_campaign { "Retry" } else { "Continue" }))
+        .observe(
+            |_: Trigger<FocusedInput<KeyboardInput>>,
+             keyboard_input: Res<ButtonInput<KeyCode>>,
+             mut next_screen: ResMut<NextState<Screen>>| {
+                if keyboard_input.just_pressed(KeyCode::Enter) {
+                    next_screen.set(Screen::Gameplay);
+                }
+            },
+        )
+        .id()]);
+
+    if in_campaign {
+        entries.push(
+            commands
+                .spawn(button("Levels"))
+                .observe(
+                    |_: Trigger<FocusedInput<KeyboardInput>>,
+                     keyboard_input: Res<ButtonInput<KeyCode>>,
+                     mut next_menu: ResMut<NextState<Menu>>| {
+                        if keyboard_input.just_pressed(KeyCode::Enter) {
+                            next_menu.set(Menu::Campaign);
+                        }
+                    },
+                )
+                .id(),
+        );
+    }
+
+    entries.extend([
         commands
-            .spawn(button("Continue"))
+            .spawn(button("Analytics"))
             .observe(
                 |_: Trigger<FocusedInput<KeyboardInput>>,
                  keyboard_input: Res<ButtonInput<KeyCode>>,
-                 mut next_screen: ResMut<NextState<Screen>>| {
+                 mut next_menu: ResMut<NextState<Menu>>| {
                     if keyboard_input.just_pressed(KeyCode::Enter) {
-                        next_screen.set(Screen::Gameplay);
+                        next_menu.set(Menu::Analytics);
                     }
                 },
             )
@@ -35,14 +115,19 @@ fn spawn_pause_menu(
             .observe(
                 |_: Trigger<FocusedInput<KeyboardInput>>,
                  keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mode: Res<GameMode>,
                  mut next_screen: ResMut<NextState<Screen>>| {
                     if keyboard_input.just_pressed(KeyCode::Enter) {
-                        next_screen.set(Screen::Title);
+                        next_screen.set(if *mode == GameMode::Custom {
+                            Screen::Editor
+                        } else {
+                            Screen::Title
+                        });
                     }
                 },
             )
             .id(),
-    ];
+    ]);
     dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
     input_focus.set(entries[0]);
 
@@ -58,6 +143,14 @@ fn spawn_pause_menu(
         },
         BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
         Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                })
+                .add_children(&lines);
             parent
                 .spawn(Node {
                     flex_direction: FlexDirection::Column,
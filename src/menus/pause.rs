@@ -6,17 +6,77 @@ use bevy::{
     prelude::*,
 };
 
-use crate::{menus::Menu, screens::Screen, ui::button};
+use crate::{
+    game::{
+        GameMode, RestartRun,
+        cat::{Cat, Segments},
+        level::Level,
+        lives::Lives,
+        savegame::save_game,
+        score::Score,
+        stats::RunTally,
+    },
+    menus::Menu,
+    screens::Screen,
+    settings::Settings,
+    ui::{button, confirm_dialog},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Pause), spawn_pause_menu);
 }
 
+fn pause_line(text: String) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont::from_font_size(24.0),
+        TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+    )
+}
+
 fn spawn_pause_menu(
     mut commands: Commands,
     mut dir_nav_map: ResMut<DirectionalNavigationMap>,
     mut input_focus: ResMut<InputFocus>,
+    cat: Query<&Segments, With<Cat>>,
+    (score, lives, tally, level, settings): (
+        Res<Score>,
+        Res<Lives>,
+        Res<RunTally>,
+        Res<Level>,
+        Res<Settings>,
+    ),
 ) {
+    let seconds = tally.play_time as u32;
+    let bindings = settings.key_bindings;
+    let mut lines = vec![
+        pause_line("Run".to_string()),
+        pause_line(format!("Score: {}", **score)),
+        pause_line(format!(
+            "Length: {}",
+            cat.single().map_or(0, |segments| segments.len())
+        )),
+        pause_line(format!("Food eaten: {}", tally.food_eaten)),
+        pause_line(format!("Time: {:02}:{:02}", seconds / 60, seconds % 60)),
+    ];
+    if level.rules.lives {
+        lines.push(pause_line(format!("Lives: {}", **lives)));
+    }
+    lines.extend([
+        pause_line(String::new()),
+        pause_line("Controls".to_string()),
+        pause_line(format!(
+            "Move: {:?} {:?} {:?} {:?}",
+            bindings.up, bindings.down, bindings.left, bindings.right
+        )),
+        pause_line("Pause: Escape".to_string()),
+        pause_line("Menu: ArrowUp ArrowDown Enter".to_string()),
+    ]);
+    let lines = lines
+        .into_iter()
+        .map(|line| commands.spawn(line).id())
+        .collect::<Vec<_>>();
+
     let entries = [
         commands
             .spawn(button("Resume"))
@@ -31,17 +91,61 @@ fn spawn_pause_menu(
             )
             .id(),
         commands
-            .spawn(button("Quit"))
+            .spawn(button("Restart"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut commands: Commands| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        commands.spawn((
+                            StateScoped(Menu::Pause),
+                            confirm_dialog("Restart this run?", restart_run),
+                        ));
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Settings"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Settings);
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Save & Quit"))
             .observe(
                 |_: Trigger<FocusedInput<KeyboardInput>>,
                  keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut commands: Commands,
                  mut next_screen: ResMut<NextState<Screen>>| {
                     if keyboard_input.just_pressed(KeyCode::Enter) {
+                        commands.run_system_cached(save_game);
                         next_screen.set(Screen::Title);
                     }
                 },
             )
             .id(),
+        commands
+            .spawn(button("Quit"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut commands: Commands| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        commands.spawn((
+                            StateScoped(Menu::Pause),
+                            confirm_dialog("Quit this run?", quit_run),
+                        ));
+                    }
+                },
+            )
+            .id(),
     ];
 
     dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
@@ -61,10 +165,37 @@ fn spawn_pause_menu(
         Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
             parent
                 .spawn(Node {
-                    flex_direction: FlexDirection::Column,
+                    column_gap: Val::Px(60.0),
+                    align_items: AlignItems::Center,
                     ..default()
                 })
-                .add_children(&entries);
+                .with_children(|parent| {
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        })
+                        .add_children(&entries);
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        })
+                        .add_children(&lines);
+                });
         })),
     ));
 }
+
+fn restart_run(mut commands: Commands, mut next_screen: ResMut<NextState<Screen>>) {
+    commands.run_schedule(RestartRun);
+    next_screen.set(Screen::Gameplay);
+}
+
+fn quit_run(mode: Res<GameMode>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(if *mode == GameMode::Custom {
+        Screen::Editor
+    } else {
+        Screen::Title
+    });
+}
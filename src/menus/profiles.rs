@@ -0,0 +1,315 @@
+use bevy::{
+    ecs::spawn::SpawnWith,
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use crate::{
+    menus::Menu,
+    profiles::{ActiveProfile, MAX_NAME_LENGTH, Profiles},
+    ui::{TextInput, button, confirm_dialog, edit_text_input, text_input},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<EditingProfile>();
+    app.add_systems(OnEnter(Menu::Profiles), spawn_profiles_menu);
+    app.add_systems(OnEnter(Menu::Profile), spawn_profile_menu);
+    app.add_systems(OnEnter(Menu::ProfileName), spawn_profile_name_menu);
+}
+
+#[derive(Resource, Default, Deref, DerefMut, Debug)]
+struct EditingProfile(Option<u32>);
+
+fn menu_root(menu: Menu, entries: Vec<Entity>) -> impl Bundle {
+    (
+        StateScoped(menu),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                })
+                .add_children(&entries);
+        })),
+    )
+}
+
+fn spawn_profiles_menu(
+    mut commands: Commands,
+    mut dir_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    profiles: Res<Profiles>,
+    active_profile: Res<ActiveProfile>,
+) {
+    let mut focused = None;
+    let mut entries = profiles
+        .profiles
+        .iter()
+        .map(|profile| {
+            let id = profile.id;
+            let entity = commands
+                .spawn(button(&profile.name))
+                .observe(
+                    move |_: Trigger<FocusedInput<KeyboardInput>>,
+                          keyboard_input: Res<ButtonInput<KeyCode>>,
+                          mut profiles: ResMut<Profiles>,
+                          mut active_profile: ResMut<ActiveProfile>,
+                          mut next_menu: ResMut<NextState<Menu>>| {
+                        if keyboard_input.just_pressed(KeyCode::Enter) {
+                            profiles.last = Some(id);
+                            **active_profile = Some(id);
+                            next_menu.set(Menu::Main);
+                        }
+                    },
+                )
+                .id();
+            if profiles.last == Some(id) {
+                focused = Some(entity);
+            }
+            entity
+        })
+        .collect::<Vec<_>>();
+
+    entries.push(
+        commands
+            .spawn(button("New Profile"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut editing: ResMut<EditingProfile>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        **editing = None;
+                        next_menu.set(Menu::ProfileName);
+                    }
+                },
+            )
+            .id(),
+    );
+
+    if active_profile.is_some() {
+        entries.push(
+            commands
+                .spawn(button("Back"))
+                .observe(
+                    |_: Trigger<FocusedInput<KeyboardInput>>,
+                     keyboard_input: Res<ButtonInput<KeyCode>>,
+                     mut next_menu: ResMut<NextState<Menu>>| {
+                        if keyboard_input.just_pressed(KeyCode::Enter) {
+                            next_menu.set(Menu::Profile);
+                        }
+                    },
+                )
+                .id(),
+        );
+    }
+
+    entries.push(
+        commands
+            .spawn(button("Quit"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut event_writer: EventWriter<AppExit>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        event_writer.write(AppExit::Success);
+                    }
+                },
+            )
+            .id(),
+    );
+
+    dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
+    input_focus.set(focused.unwrap_or(entries[0]));
+
+    commands.spawn(menu_root(Menu::Profiles, entries));
+}
+
+fn spawn_profile_menu(
+    mut commands: Commands,
+    mut dir_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+) {
+    let entries = vec![
+        commands
+            .spawn(button("Rename"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 active_profile: Res<ActiveProfile>,
+                 mut editing: ResMut<EditingProfile>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        **editing = **active_profile;
+                        next_menu.set(Menu::ProfileName);
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Delete"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut commands: Commands,
+                 profiles: Res<Profiles>,
+                 active_profile: Res<ActiveProfile>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        let name = active_profile
+                            .and_then(|id| profiles.name(id))
+                            .unwrap_or_default();
+                        commands.spawn((
+                            StateScoped(Menu::Profile),
+                            confirm_dialog(&format!("Delete profile {name}?"), delete_profile),
+                        ));
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Switch"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Profiles);
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Back"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Main);
+                    }
+                },
+            )
+            .id(),
+    ];
+
+    dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
+    input_focus.set(entries[0]);
+
+    commands.spawn(menu_root(Menu::Profile, entries));
+}
+
+fn delete_profile(
+    mut profiles: ResMut<Profiles>,
+    mut active_profile: ResMut<ActiveProfile>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    if let Some(id) = active_profile.take() {
+        profiles.delete(id);
+    }
+    next_menu.set(Menu::Profiles);
+}
+
+fn spawn_profile_name_menu(
+    mut commands: Commands,
+    mut dir_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    profiles: Res<Profiles>,
+    editing: Res<EditingProfile>,
+) {
+    let name = editing.and_then(|id| profiles.name(id)).unwrap_or_default();
+
+    let input = commands
+        .spawn(text_input(name, MAX_NAME_LENGTH))
+        .observe(edit_text_input)
+        .observe(
+            |trigger: Trigger<FocusedInput<KeyboardInput>>,
+             keyboard_input: Res<ButtonInput<KeyCode>>,
+             inputs: Query<&TextInput>,
+             mut commands: Commands| {
+                if keyboard_input.just_pressed(KeyCode::Enter)
+                    && let Ok(input) = inputs.get(trigger.target())
+                {
+                    commands.run_system_cached_with(save_profile_name, input.value.clone());
+                }
+            },
+        )
+        .id();
+
+    let entries = vec![
+        input,
+        commands
+            .spawn(button("Save"))
+            .observe(
+                move |_: Trigger<FocusedInput<KeyboardInput>>,
+                      keyboard_input: Res<ButtonInput<KeyCode>>,
+                      inputs: Query<&TextInput>,
+                      mut commands: Commands| {
+                    if keyboard_input.just_pressed(KeyCode::Enter)
+                        && let Ok(input) = inputs.get(input)
+                    {
+                        commands.run_system_cached_with(save_profile_name, input.value.clone());
+                    }
+                },
+            )
+            .id(),
+        commands
+            .spawn(button("Cancel"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 editing: Res<EditingProfile>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(if editing.is_some() {
+                            Menu::Profile
+                        } else {
+                            Menu::Profiles
+                        });
+                    }
+                },
+            )
+            .id(),
+    ];
+
+    dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
+    input_focus.set(entries[0]);
+
+    commands.spawn(menu_root(Menu::ProfileName, entries));
+}
+
+fn save_profile_name(
+    In(name): In<String>,
+    editing: Res<EditingProfile>,
+    mut profiles: ResMut<Profiles>,
+    mut active_profile: ResMut<ActiveProfile>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return;
+    }
+
+    match **editing {
+        Some(id) => {
+            profiles.rename(id, name);
+            next_menu.set(Menu::Profile);
+        }
+        None => {
+            let id = profiles.create(name);
+            profiles.last = Some(id);
+            **active_profile = Some(id);
+            next_menu.set(Menu::Main);
+        }
+    }
+}
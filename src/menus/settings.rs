@@ -0,0 +1,193 @@
+use bevy::{
+    ecs::spawn::SpawnWith,
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use crate::{
+    menus::Menu,
+    screens::Screen,
+    settings::{MAX_COUNTDOWN, MAX_FOOD_COUNT, Settings},
+    ui::button,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
+    app.add_systems(
+        Update,
+        update_settings_labels.run_if(in_state(Menu::Settings).and(resource_changed::<Settings>)),
+    );
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+enum SettingsEntry {
+    Lives,
+    FoodCount,
+    SmoothMovement,
+    Telemetry,
+    Difficulty,
+    Countdown,
+    AutoPause,
+}
+
+impl SettingsEntry {
+    fn label(self, settings: &Settings) -> String {
+        match self {
+            SettingsEntry::Lives => format!("Lives: {}", on_off(settings.lives)),
+            SettingsEntry::FoodCount => format!("Food: {}", settings.food_count),
+            SettingsEntry::SmoothMovement => {
+                format!("Smooth: {}", on_off(settings.smooth_movement))
+            }
+            SettingsEntry::Telemetry => format!("Telemetry: {}", on_off(settings.telemetry)),
+            SettingsEntry::Difficulty => format!("Difficulty: {}", settings.difficulty.label()),
+            SettingsEntry::Countdown => match settings.countdown {
+                0 => "Countdown: Off".to_string(),
+                seconds => format!("Countdown: {seconds}s"),
+            },
+            SettingsEntry::AutoPause => format!("Auto Pause: {}", on_off(settings.auto_pause)),
+        }
+    }
+
+    fn toggle(self, settings: &mut Settings) {
+        match self {
+            SettingsEntry::Lives => settings.lives = !settings.lives,
+            SettingsEntry::FoodCount => {
+                settings.food_count = settings.food_count % MAX_FOOD_COUNT + 1;
+            }
+            SettingsEntry::SmoothMovement => settings.smooth_movement = !settings.smooth_movement,
+            SettingsEntry::Telemetry => settings.telemetry = !settings.telemetry,
+            SettingsEntry::Difficulty => settings.difficulty = settings.difficulty.next(),
+            SettingsEntry::Countdown => {
+                settings.countdown = (settings.countdown + 1) % (MAX_COUNTDOWN + 1);
+            }
+            SettingsEntry::AutoPause => settings.auto_pause = !settings.auto_pause,
+        }
+    }
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value { "On" } else { "Off" }
+}
+
+fn spawn_settings_menu(
+    mut commands: Commands,
+    mut dir_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    settings: Res<Settings>,
+) {
+    let mut entries = [
+        SettingsEntry::Lives,
+        SettingsEntry::FoodCount,
+        SettingsEntry::SmoothMovement,
+        SettingsEntry::Telemetry,
+        SettingsEntry::Difficulty,
+        SettingsEntry::Countdown,
+        SettingsEntry::AutoPause,
+    ]
+    .map(|entry| {
+        commands
+            .spawn((entry, button(&entry.label(&settings))))
+            .observe(
+                move |_: Trigger<FocusedInput<KeyboardInput>>,
+                      keyboard_input: Res<ButtonInput<KeyCode>>,
+                      mut settings: ResMut<Settings>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        entry.toggle(&mut settings);
+                    }
+                },
+            )
+            .id()
+    })
+    .to_vec();
+
+    entries.push(
+        commands
+            .spawn(button("Mods"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Mods);
+                    }
+                },
+            )
+            .id(),
+    );
+
+    entries.push(
+        commands
+            .spawn(button("Controls"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(Menu::Controls);
+                    }
+                },
+            )
+            .id(),
+    );
+
+    entries.push(
+        commands
+            .spawn(button("Back"))
+            .observe(
+                |_: Trigger<FocusedInput<KeyboardInput>>,
+                 keyboard_input: Res<ButtonInput<KeyCode>>,
+                 screen: Res<State<Screen>>,
+                 mut next_menu: ResMut<NextState<Menu>>| {
+                    if keyboard_input.just_pressed(KeyCode::Enter) {
+                        next_menu.set(previous_menu(*screen.get()));
+                    }
+                },
+            )
+            .id(),
+    );
+
+    dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
+    input_focus.set(entries[0]);
+
+    commands.spawn((
+        StateScoped(Menu::Settings),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                })
+                .add_children(&entries);
+        })),
+    ));
+}
+
+fn previous_menu(screen: Screen) -> Menu {
+    match screen {
+        Screen::Pause => Menu::Pause,
+        _ => Menu::Main,
+    }
+}
+
+fn update_settings_labels(
+    entries: Query<(&SettingsEntry, &Children)>,
+    mut text: Query<&mut Text>,
+    settings: Res<Settings>,
+) {
+    for (entry, children) in &entries {
+        if let Ok(mut text) = text.get_mut(children[0]) {
+            text.0 = entry.label(&settings);
+        }
+    }
+}
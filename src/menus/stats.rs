@@ -0,0 +1,135 @@
+use bevy::{
+    ecs::spawn::SpawnWith,
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use crate::{
+    game::{
+        GameMode,
+        score::HighScores,
+        stats::{LifetimeStats, ModeStats},
+    },
+    menus::Menu,
+    ui::button,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Stats), spawn_stats_menu);
+}
+
+fn format_play_time(seconds: f32) -> String {
+    let seconds = seconds as u64;
+    format!(
+        "{}h {:02}m {:02}s",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn mode_summary(label: &str, stats: &ModeStats) -> String {
+    format!(
+        "{label}: {} games, {} fish, longest {}, {}",
+        stats.games_played,
+        stats.fish_eaten,
+        stats.longest_cat,
+        format_play_time(stats.play_time)
+    )
+}
+
+fn stat_line(text: String, font_size: f32) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont::from_font_size(font_size),
+        TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+    )
+}
+
+fn spawn_stats_menu(
+    mut commands: Commands,
+    mut dir_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    stats: Res<LifetimeStats>,
+    high_scores: Res<HighScores>,
+) {
+    let mut lines = vec![
+        format!("Games played: {}", stats.total.games_played),
+        format!("Fish eaten: {}", stats.total.fish_eaten),
+        format!("Tiles travelled: {}", stats.total.tiles_travelled),
+        format!("Longest cat: {}", stats.total.longest_cat),
+        format!(
+            "Favourite death: {}",
+            stats
+                .favourite_death_cause()
+                .map_or("None", |cause| cause.label())
+        ),
+        format!("Play time: {}", format_play_time(stats.total.play_time)),
+        format!(
+            "High scores: {}",
+            high_scores
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    ]
+    .into_iter()
+    .map(|line| commands.spawn(stat_line(line, 30.0)).id())
+    .collect::<Vec<_>>();
+
+    lines.extend(GameMode::ALL.iter().filter_map(|mode| {
+        let mode_stats = stats.modes.get(mode)?;
+        Some(
+            commands
+                .spawn(stat_line(mode_summary(mode.label(), mode_stats), 20.0))
+                .id(),
+        )
+    }));
+
+    let entries = [commands
+        .spawn(button("Back"))
+        .observe(
+            |_: Trigger<FocusedInput<KeyboardInput>>,
+             keyboard_input: Res<ButtonInput<KeyCode>>,
+             mut next_menu: ResMut<NextState<Menu>>| {
+                if keyboard_input.just_pressed(KeyCode::Enter) {
+                    next_menu.set(Menu::Main);
+                }
+            },
+        )
+        .id()];
+
+    dir_nav_map.add_looping_edges(&entries, CompassOctant::South);
+    input_focus.set(entries[0]);
+
+    commands.spawn((
+        StateScoped(Menu::Stats),
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                })
+                .add_children(&lines);
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                })
+                .add_children(&entries);
+        })),
+    ));
+}
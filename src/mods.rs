@@ -0,0 +1,548 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    asset::{
+        AssetLoader, LoadContext,
+        io::{
+            AssetReader, AssetReaderError, AssetSource, AssetSourceId, ErasedAssetReader,
+            PathStream, Reader,
+        },
+    },
+    prelude::*,
+    tasks::futures_lite::{StreamExt, stream},
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+#[cfg(feature = "dev")]
+use {bevy::asset::io::AssetWatcher, std::time::Duration};
+
+use crate::{
+    game::{
+        achievements::{AchievementDefinition, AchievementRegistry},
+        atlas::AtlasMetadata,
+        campaign::Campaign,
+        events::LevelChanged,
+        food::{FoodDefinition, FoodRegistry},
+        level::{CLASSIC_LEVEL, Level, LoadedLevels},
+        tuning::Tuning,
+    },
+    storage,
+    theme::Theme,
+};
+
+const MODS_DIR: &str = "mods";
+const MANIFEST_FILE: &str = "mod.json";
+const MOD_LIST_FILE: &str = "mods.json";
+const BUILTIN_ASSETS: &str = "assets";
+const FOODS_FILE: &str = "foods.json";
+const ACHIEVEMENTS_FILE: &str = "achievements.json";
+const THEME_FILE: &str = "theme.json";
+const TUNING_FILE: &str = "tuning.json";
+const ATLAS_FILE: &str = "atlas.json";
+const CAMPAIGN_FILE: &str = "campaign.json";
+const FONT_FILE: &str = "fonts/main.ttf";
+const LEVELS_DIR: &str = "levels/";
+#[cfg(feature = "dev")]
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+pub fn register_asset_source(app: &mut App) {
+    let mods = Mods::discover();
+    let layers = mods
+        .loaded_mods()
+        .map(|info| info.dir.to_string_lossy().into_owned())
+        .chain([BUILTIN_ASSETS.to_string()])
+        .collect::<Vec<_>>();
+
+    let readers = layers.clone();
+    let source = AssetSource::build().with_reader(move || {
+        Box::new(LayeredAssetReader {
+            layers: readers
+                .iter()
+                .map(|path| AssetSource::get_default_reader(path.clone())())
+                .collect(),
+        })
+    });
+    #[cfg(feature = "dev")]
+    let source = source.with_watcher(move |sender| {
+        let watchers = layers
+            .iter()
+            .filter_map(|path| {
+                AssetSource::get_default_watcher(path.clone(), WATCH_DEBOUNCE)(sender.clone())
+            })
+            .collect::<Vec<_>>();
+        Some(Box::new(LayeredAssetWatcher { _layers: watchers }))
+    });
+    app.register_asset_source(AssetSourceId::Default, source);
+    app.insert_resource(mods);
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<FoodDefinitions>();
+    app.init_asset::<AchievementDefinitions>();
+    app.init_asset::<Theme>();
+    app.init_asset::<Level>();
+    app.init_asset::<Tuning>();
+    app.init_asset::<AtlasMetadata>();
+    app.init_asset::<Campaign>();
+    app.init_asset_loader::<JsonLoader<FoodDefinitions>>();
+    app.init_asset_loader::<JsonLoader<AchievementDefinitions>>();
+    app.init_asset_loader::<JsonLoader<Theme>>();
+    app.init_asset_loader::<JsonLoader<Level>>();
+    app.init_asset_loader::<JsonLoader<Tuning>>();
+    app.init_asset_loader::<JsonLoader<AtlasMetadata>>();
+    app.init_asset_loader::<JsonLoader<Campaign>>();
+
+    app.add_systems(Startup, load_mod_content);
+    app.add_systems(
+        Update,
+        (
+            apply_mod_foods,
+            apply_mod_achievements,
+            apply_mod_resource::<Theme>,
+            apply_mod_resource::<Tuning>,
+            apply_mod_resource::<AtlasMetadata>,
+            apply_mod_resource::<Campaign>,
+            load_campaign_levels.run_if(resource_changed::<Campaign>),
+            apply_mod_levels,
+        )
+            .chain()
+            .run_if(resource_exists::<ModContent>),
+    );
+    app.add_systems(
+        PostUpdate,
+        apply_mod_font.run_if(resource_exists::<ModContent>),
+    );
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ModManifest {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub load_order: i32,
+}
+
+#[derive(Clone, Debug)]
+pub struct ModInfo {
+    pub manifest: ModManifest,
+    dir: PathBuf,
+    files: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+struct ModList {
+    disabled: Vec<String>,
+}
+
+#[derive(Resource, Debug)]
+pub struct Mods {
+    pub mods: Vec<ModInfo>,
+    pub problems: Vec<String>,
+    disabled: Vec<String>,
+    loaded: Vec<String>,
+}
+
+fn list_files(root: &Path, dir: &Path, files: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            list_files(root, &path, files);
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let relative = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            if relative != MANIFEST_FILE {
+                files.push(relative);
+            }
+        }
+    }
+}
+
+impl Mods {
+    pub fn dir() -> Option<PathBuf> {
+        storage::data_dir().map(|dir| dir.join(MODS_DIR))
+    }
+
+    fn discover() -> Self {
+        let mut mods = Vec::<ModInfo>::new();
+        let mut problems = Vec::new();
+
+        let entries = Self::dir()
+            .and_then(|dir| fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir());
+        for dir in entries {
+            let folder = dir
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned();
+            let manifest = fs::read_to_string(dir.join(MANIFEST_FILE))
+                .map_err(|e| e.to_string())
+                .and_then(|contents| {
+                    serde_json::from_str::<ModManifest>(&contents).map_err(|e| e.to_string())
+                });
+            let manifest = match manifest {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    problems.push(format!("{folder}: invalid {MANIFEST_FILE}: {e}"));
+                    continue;
+                }
+            };
+            if mods.iter().any(|info| info.manifest.name == manifest.name) {
+                problems.push(format!(
+                    "{folder}: another mod is already named {}",
+                    manifest.name
+                ));
+                continue;
+            }
+
+            let mut files = Vec::new();
+            list_files(&dir, &dir, &mut files);
+            files.sort();
+            mods.push(ModInfo {
+                manifest,
+                dir,
+                files,
+            });
+        }
+        mods.sort_by(|a, b| {
+            (a.manifest.load_order, &a.manifest.name)
+                .cmp(&(b.manifest.load_order, &b.manifest.name))
+        });
+
+        let disabled = storage::load::<ModList>(None, MOD_LIST_FILE)
+            .unwrap_or_default()
+            .disabled;
+        let loaded = mods
+            .iter()
+            .map(|info| info.manifest.name.clone())
+            .filter(|name| !disabled.contains(name))
+            .collect();
+        for info in &mods {
+            info!(
+                "found mod {} {} (load order {})",
+                info.manifest.name, info.manifest.version, info.manifest.load_order
+            );
+        }
+
+        Self {
+            mods,
+            problems,
+            disabled,
+            loaded,
+        }
+    }
+
+    pub fn enabled(&self, name: &str) -> bool {
+        !self.disabled.iter().any(|disabled| disabled == name)
+    }
+
+    pub fn toggle(&mut self, name: &str) {
+        if self.enabled(name) {
+            self.disabled.push(name.to_string());
+        } else {
+            self.disabled.retain(|disabled| disabled != name);
+        }
+        storage::save(
+            None,
+            MOD_LIST_FILE,
+            &ModList {
+                disabled: self.disabled.clone(),
+            },
+        );
+    }
+
+    pub fn needs_restart(&self) -> bool {
+        self.mods.iter().any(|info| {
+            self.enabled(&info.manifest.name) != self.loaded.contains(&info.manifest.name)
+        })
+    }
+
+    fn loaded_mods(&self) -> impl Iterator<Item = &ModInfo> {
+        self.mods
+            .iter()
+            .rev()
+            .filter(|info| self.loaded.contains(&info.manifest.name))
+    }
+
+    fn provides(&self, file: &str) -> bool {
+        self.loaded_mods()
+            .any(|info| info.files.iter().any(|provided| provided == file))
+    }
+
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut providers = HashMap::<&str, Vec<&str>>::new();
+        for info in self
+            .mods
+            .iter()
+            .filter(|info| self.enabled(&info.manifest.name))
+        {
+            for file in &info.files {
+                providers.entry(file).or_default().push(&info.manifest.name);
+            }
+        }
+
+        let mut conflicts = providers
+            .into_iter()
+            .filter(|(_, names)| names.len() > 1)
+            .map(|(file, names)| {
+                format!(
+                    "{file}: {} conflict, {} wins",
+                    names.join(", "),
+                    names[names.len() - 1]
+                )
+            })
+            .collect::<Vec<_>>();
+        conflicts.sort();
+        conflicts
+    }
+}
+
+#[cfg(feature = "dev")]
+struct LayeredAssetWatcher {
+    _layers: Vec<Box<dyn AssetWatcher>>,
+}
+
+#[cfg(feature = "dev")]
+impl AssetWatcher for LayeredAssetWatcher {}
+
+struct LayeredAssetReader {
+    layers: Vec<Box<dyn ErasedAssetReader>>,
+}
+
+impl AssetReader for LayeredAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        for layer in &self.layers {
+            match layer.read(path).await {
+                Err(AssetReaderError::NotFound(_)) => continue,
+                result => return result,
+            }
+        }
+        Err(AssetReaderError::NotFound(path.to_path_buf()))
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<impl Reader + 'a, AssetReaderError> {
+        for layer in &self.layers {
+            match layer.read_meta(path).await {
+                Err(AssetReaderError::NotFound(_)) => continue,
+                result => return result,
+            }
+        }
+        Err(AssetReaderError::NotFound(path.to_path_buf()))
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let mut found = false;
+        let mut paths = Vec::new();
+        for layer in &self.layers {
+            match layer.read_directory(path).await {
+                Ok(mut entries) => {
+                    found = true;
+                    while let Some(entry) = entries.next().await {
+                        if !paths.contains(&entry) {
+                            paths.push(entry);
+                        }
+                    }
+                }
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        if found {
+            Ok(Box::new(stream::iter(paths)))
+        } else {
+            Err(AssetReaderError::NotFound(path.to_path_buf()))
+        }
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        for layer in &self.layers {
+            if let Ok(true) = layer.is_directory(path).await {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct FoodDefinitions(Vec<FoodDefinition>);
+
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct AchievementDefinitions(Vec<AchievementDefinition>);
+
+struct JsonLoader<A>(PhantomData<fn() -> A>);
+
+impl<A> Default for JsonLoader<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Asset + DeserializeOwned> AssetLoader for JsonLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<A, io::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        serde_json::from_slice(&bytes).map_err(io::Error::from)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+struct ModContent {
+    foods: Handle<FoodDefinitions>,
+    achievements: Handle<AchievementDefinitions>,
+    resources: Vec<UntypedHandle>,
+    font: Option<Handle<Font>>,
+    levels: HashMap<String, Handle<Level>>,
+}
+
+fn load_mod_content(mut commands: Commands, asset_server: Res<AssetServer>, mods: Res<Mods>) {
+    let levels = HashMap::from([(
+        CLASSIC_LEVEL.to_string(),
+        asset_server.load(format!("{LEVELS_DIR}{CLASSIC_LEVEL}.json")),
+    )]);
+
+    commands.insert_resource(ModContent {
+        foods: asset_server.load(FOODS_FILE),
+        achievements: asset_server.load(ACHIEVEMENTS_FILE),
+        resources: vec![
+            asset_server.load::<Theme>(THEME_FILE).untyped(),
+            asset_server.load::<Tuning>(TUNING_FILE).untyped(),
+            asset_server.load::<AtlasMetadata>(ATLAS_FILE).untyped(),
+            asset_server.load::<Campaign>(CAMPAIGN_FILE).untyped(),
+        ],
+        font: mods
+            .provides(FONT_FILE)
+            .then(|| asset_server.load(FONT_FILE)),
+        levels,
+    });
+}
+
+fn changed_asset<A: Asset>(event: &AssetEvent<A>) -> Option<AssetId<A>> {
+    match *event {
+        AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(id),
+        _ => None,
+    }
+}
+
+fn apply_mod_foods(
+    mut events: EventReader<AssetEvent<FoodDefinitions>>,
+    content: Res<ModContent>,
+    assets: Res<Assets<FoodDefinitions>>,
+    mut registry: ResMut<FoodRegistry>,
+) {
+    for id in events.read().filter_map(changed_asset) {
+        if content.foods.id() == id
+            && let Some(foods) = assets.get(id)
+        {
+            *registry = FoodRegistry::new(foods.0.clone());
+        }
+    }
+}
+
+fn apply_mod_achievements(
+    mut events: EventReader<AssetEvent<AchievementDefinitions>>,
+    content: Res<ModContent>,
+    assets: Res<Assets<AchievementDefinitions>>,
+    mut registry: ResMut<AchievementRegistry>,
+) {
+    for id in events.read().filter_map(changed_asset) {
+        if content.achievements.id() == id
+            && let Some(achievements) = assets.get(id)
+        {
+            registry.achievements = achievements.0.clone();
+        }
+    }
+}
+
+fn apply_mod_resource<A: Asset + Resource + Clone>(
+    mut events: EventReader<AssetEvent<A>>,
+    content: Res<ModContent>,
+    assets: Res<Assets<A>>,
+    mut resource: ResMut<A>,
+) {
+    for id in events.read().filter_map(changed_asset) {
+        if content
+            .resources
+            .iter()
+            .any(|handle| handle.id() == id.untyped())
+            && let Some(loaded) = assets.get(id)
+        {
+            *resource = loaded.clone();
+        }
+    }
+}
+
+fn load_campaign_levels(
+    asset_server: Res<AssetServer>,
+    campaign: Res<Campaign>,
+    mut content: ResMut<ModContent>,
+) {
+    for level in &campaign.levels {
+        content
+            .levels
+            .entry(level.id.clone())
+            .or_insert_with(|| asset_server.load(format!("{LEVELS_DIR}{}.json", level.id)));
+    }
+}
+
+fn apply_mod_levels(
+    mut events: EventReader<AssetEvent<Level>>,
+    content: Res<ModContent>,
+    assets: Res<Assets<Level>>,
+    mut levels: ResMut<LoadedLevels>,
+    mut level_changed: EventWriter<LevelChanged>,
+) {
+    for id in events.read().filter_map(changed_asset) {
+        for (name, handle) in &content.levels {
+            if handle.id() == id
+                && let Some(level) = assets.get(id)
+                && levels.get(name) != Some(level)
+            {
+                levels.insert(name.clone(), level.clone());
+                level_changed.write(LevelChanged { id: name.clone() });
+            }
+        }
+    }
+}
+
+fn apply_mod_font(mut texts: Query<&mut TextFont, Added<TextFont>>, content: Res<ModContent>) {
+    let Some(font) = &content.font else {
+        return;
+    };
+    for mut text_font in &mut texts {
+        text_font.font = font.clone();
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::storage;
+
+pub const MAX_NAME_LENGTH: usize = 16;
+
+const PROFILES_FILE: &str = "profiles.json";
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(storage::load::<Profiles>(None, PROFILES_FILE).unwrap_or_default());
+    app.init_resource::<ActiveProfile>();
+    app.add_systems(
+        Update,
+        save_profiles.run_if(resource_changed::<Profiles>.and(not(resource_added::<Profiles>))),
+    );
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Profile {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+#[serde(default)]
+pub struct Profiles {
+    pub profiles: Vec<Profile>,
+    pub last: Option<u32>,
+    next_id: u32,
+}
+
+impl Profiles {
+    pub fn create(&mut self, name: String) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.profiles.push(Profile { id, name });
+        id
+    }
+
+    pub fn rename(&mut self, id: u32, name: String) {
+        if let Some(profile) = self.profiles.iter_mut().find(|profile| profile.id == id) {
+            profile.name = name;
+        }
+    }
+
+    pub fn delete(&mut self, id: u32) {
+        self.profiles.retain(|profile| profile.id != id);
+        if self.last == Some(id) {
+            self.last = None;
+        }
+        storage::remove_profile(id);
+    }
+
+    pub fn name(&self, id: u32) -> Option<&str> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.id == id)
+            .map(|profile| profile.name.as_str())
+    }
+}
+
+#[derive(Resource, Default, Deref, DerefMut, Clone, Copy, PartialEq, Debug)]
+pub struct ActiveProfile(pub Option<u32>);
+
+pub fn load_profile_data<T: Resource + DeserializeOwned + Default>(
+    name: &'static str,
+) -> impl System<In = (), Out = ()> {
+    IntoSystem::into_system(move |mut commands: Commands, profile: Res<ActiveProfile>| {
+        commands.insert_resource(storage::load::<T>(**profile, name).unwrap_or_default());
+    })
+}
+
+fn save_profiles(profiles: Res<Profiles>) {
+    storage::save(None, PROFILES_FILE, &*profiles);
+}
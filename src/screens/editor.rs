@@ -0,0 +1,463 @@
+use std::mem;
+
+use bevy::{prelude::*, window::WindowResized};
+
+use crate::{
+    game::{
+        GameState, InGame,
+        cat::Direction,
+        grid::{Cell, CellSize, board_cell, move_camera_to_grid, setup_cell_size},
+        level::{CUSTOM_LEVEL_FILE, Level, Tile, TileKind},
+    },
+    profiles::ActiveProfile,
+    screens::Screen,
+    settings::MAX_FOOD_COUNT,
+    storage,
+    theme::Theme,
+};
+
+const MIN_GRID_CELLS: IVec2 = IVec2::new(5, 5);
+const MAX_GRID_CELLS: IVec2 = IVec2::new(40, 24);
+const MAX_UNDO: usize = 100;
+const OVERLAY_DEPTH: f32 = 3.0;
+const START_COLOR: Color = Color::srgb_u8(0xe0, 0x90, 0x40);
+
+const TOOL_KEYS: [(KeyCode, Tool); 6] = [
+    (KeyCode::Digit1, Tool::Wall),
+    (KeyCode::Digit2, Tool::Portal),
+    (KeyCode::Digit3, Tool::Boost),
+    (KeyCode::Digit4, Tool::Slow),
+    (KeyCode::Digit5, Tool::Start),
+    (KeyCode::Digit0, Tool::Erase),
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Editor>();
+
+    app.add_systems(OnEnter(Screen::Editor), enter_editor);
+    app.add_systems(
+        OnTransition {
+            exited: Screen::Editor,
+            entered: Screen::Title,
+        },
+        reset_editor,
+    );
+
+    app.add_systems(
+        Update,
+        (
+            handle_editor_input,
+            (setup_cell_size, move_camera_to_grid, draw_level)
+                .chain()
+                .run_if(
+                    resource_changed::<Level>
+                        .or(resource_changed::<Editor>)
+                        .or(on_event::<WindowResized>),
+                ),
+            update_editor_hud.run_if(resource_changed::<Level>.or(resource_changed::<Editor>)),
+        )
+            .chain()
+            .run_if(in_state(Screen::Editor)),
+    );
+
+    app.add_observer(press_cell);
+    app.add_observer(drag_over_cell);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+enum Tool {
+    #[default]
+    Wall,
+    Portal,
+    Boost,
+    Slow,
+    Start,
+    Erase,
+}
+
+impl Tool {
+    fn label(self) -> &'static str {
+        match self {
+            Tool::Wall => "Wall",
+            Tool::Portal => "Portal",
+            Tool::Boost => "Boost",
+            Tool::Slow => "Slow",
+            Tool::Start => "Start",
+            Tool::Erase => "Erase",
+        }
+    }
+
+    fn paints(self) -> bool {
+        !matches!(self, Tool::Portal | Tool::Start)
+    }
+}
+
+#[derive(Resource, Default, Debug)]
+struct Editor {
+    tool: Tool,
+    pending_portal: Option<IVec2>,
+    undo: Vec<Level>,
+    redo: Vec<Level>,
+    stroke_recorded: bool,
+    status: String,
+}
+
+impl Editor {
+    fn commit(&mut self, level: &mut Level, edited: Level) {
+        if edited == *level {
+            return;
+        }
+
+        if !self.stroke_recorded {
+            self.undo.push(level.clone());
+            let excess = self.undo.len().saturating_sub(MAX_UNDO);
+            self.undo.drain(..excess);
+            self.redo.clear();
+            self.stroke_recorded = true;
+        }
+        *level = edited;
+        self.status.clear();
+    }
+
+    fn apply(&mut self, level: &mut Level, tool: Tool, cell: IVec2) {
+        match tool {
+            Tool::Wall => {
+                clear_cell(level, cell);
+                if level.start != cell {
+                    level.walls.push(cell);
+                }
+            }
+            Tool::Portal => match self.pending_portal.take() {
+                Some(entry) if entry != cell => {
+                    clear_cell(level, entry);
+                    clear_cell(level, cell);
+                    level.portals.push([entry, cell]);
+                }
+                Some(_) => {}
+                None => {
+                    clear_cell(level, cell);
+                    self.pending_portal = Some(cell);
+                }
+            },
+            Tool::Boost | Tool::Slow => {
+                clear_cell(level, cell);
+                let kind = if tool == Tool::Boost {
+                    TileKind::Boost
+                } else {
+                    TileKind::Slow
+                };
+                level.tiles.push(Tile { cell, kind });
+            }
+            Tool::Start => {
+                clear_cell(level, cell);
+                level.start = cell;
+            }
+            Tool::Erase => clear_cell(level, cell),
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+struct EditorCanvas;
+
+#[derive(Component, Debug)]
+struct EditorCell;
+
+#[derive(Component, Debug)]
+struct EditorHud;
+
+fn clear_cell(level: &mut Level, cell: IVec2) {
+    level.walls.retain(|wall| *wall != cell);
+    level.tiles.retain(|tile| tile.cell != cell);
+    level.portals.retain(|portal| !portal.contains(&cell));
+}
+
+fn resize_level(level: &mut Level, delta: IVec2) {
+    level.size = (level.size + delta).clamp(MIN_GRID_CELLS, MAX_GRID_CELLS);
+    let size = level.size;
+    let inside = |cell: &IVec2| cell.cmplt(size).all();
+    level.walls.retain(inside);
+    level.tiles.retain(|tile| inside(&tile.cell));
+    level.portals.retain(|portal| portal.iter().all(inside));
+    level.start = level.start.min(size - 1);
+}
+
+fn rotate(direction: Direction) -> Direction {
+    match direction {
+        Direction::Right => Direction::Down,
+        Direction::Down => Direction::Left,
+        Direction::Left => Direction::Up,
+        Direction::Up => Direction::Right,
+    }
+}
+
+fn validate(level: &Level) -> Option<&'static str> {
+    let cells = level.spawn_cells();
+    if cells
+        .iter()
+        .any(|cell| level.walls.contains(&cell.as_ivec2()))
+    {
+        Some("The cat would start on a wall")
+    } else if cells.iter().any(|cell| level.portal_exit(*cell).is_some()) {
+        Some("The cat would start on a portal")
+    } else {
+        None
+    }
+}
+
+fn overlay(position: Vec2, size: f32, scale: f32, color: Color) -> impl Bundle {
+    (
+        StateScoped(Screen::Editor),
+        EditorCanvas,
+        Pickable::IGNORE,
+        Transform::from_translation(Vec3::from((position * size, OVERLAY_DEPTH)))
+            .with_scale(Vec3::splat(size * scale)),
+        Sprite::from_color(color, Vec2::ONE),
+    )
+}
+
+fn enter_editor(
+    mut commands: Commands,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_in_game: ResMut<NextState<InGame>>,
+    mut level: ResMut<Level>,
+    mut editor: ResMut<Editor>,
+) {
+    next_game_state.set(GameState::None);
+    next_in_game.set(InGame::False);
+    level.set_changed();
+    editor.status.clear();
+
+    commands.spawn((
+        StateScoped(Screen::Editor),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            padding: UiRect::axes(Val::Px(20.0), Val::Px(10.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Pickable::IGNORE,
+        children![(
+            EditorHud,
+            Text::default(),
+            TextFont::from_font_size(20.0),
+            TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+fn reset_editor(mut editor: ResMut<Editor>) {
+    *editor = Editor::default();
+}
+
+fn handle_editor_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut editor: ResMut<Editor>,
+    mut level: ResMut<Level>,
+    profile: Res<ActiveProfile>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if let Some((_, tool)) = TOOL_KEYS
+        .iter()
+        .find(|(key, _)| keyboard_input.just_pressed(*key))
+    {
+        editor.tool = *tool;
+        editor.pending_portal = None;
+    }
+
+    if keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        if keyboard_input.just_pressed(KeyCode::KeyZ)
+            && let Some(previous) = editor.undo.pop()
+        {
+            editor.redo.push(mem::replace(&mut *level, previous));
+            editor.pending_portal = None;
+        }
+        if keyboard_input.just_pressed(KeyCode::KeyY)
+            && let Some(next) = editor.redo.pop()
+        {
+            editor.undo.push(mem::replace(&mut *level, next));
+            editor.pending_portal = None;
+        }
+        if keyboard_input.just_pressed(KeyCode::KeyS) {
+            storage::save(**profile, CUSTOM_LEVEL_FILE, &*level);
+            editor.status = "Level saved".into();
+        }
+        return;
+    }
+
+    let mut edited = level.clone();
+    if keyboard_input.just_pressed(KeyCode::KeyR) {
+        edited.direction = rotate(edited.direction);
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyL) {
+        edited.rules.lives = !edited.rules.lives;
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyF) {
+        edited.rules.food_count = edited.rules.food_count % MAX_FOOD_COUNT + 1;
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyD) {
+        edited.rules.difficulty = edited.rules.difficulty.next();
+    }
+
+    let delta = [
+        (KeyCode::ArrowLeft, IVec2::NEG_X),
+        (KeyCode::ArrowRight, IVec2::X),
+        (KeyCode::ArrowDown, IVec2::NEG_Y),
+        (KeyCode::ArrowUp, IVec2::Y),
+    ]
+    .into_iter()
+    .filter(|(key, _)| keyboard_input.just_pressed(*key))
+    .map(|(_, delta)| delta)
+    .sum::<IVec2>();
+    if delta != IVec2::ZERO {
+        resize_level(&mut edited, delta);
+    }
+
+    if edited != *level {
+        editor.stroke_recorded = false;
+        editor.pending_portal = None;
+        editor.commit(&mut level, edited);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::KeyP) {
+        match validate(&level) {
+            Some(problem) => editor.status = problem.into(),
+            None => next_screen.set(Screen::Gameplay),
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        next_screen.set(Screen::Title);
+    }
+}
+
+fn press_cell(
+    trigger: Trigger<Pointer<Pressed>>,
+    cells: Query<&Cell, With<EditorCell>>,
+    mut editor: ResMut<Editor>,
+    mut level: ResMut<Level>,
+) {
+    let Ok(cell) = cells.get(trigger.target()) else {
+        return;
+    };
+
+    let tool = match trigger.button {
+        PointerButton::Primary => editor.tool,
+        PointerButton::Secondary => Tool::Erase,
+        PointerButton::Middle => return,
+    };
+
+    let mut edited = level.clone();
+    editor.apply(&mut edited, tool, cell.as_ivec2());
+    editor.stroke_recorded = false;
+    editor.commit(&mut level, edited);
+}
+
+fn drag_over_cell(
+    trigger: Trigger<Pointer<Over>>,
+    cells: Query<&Cell, With<EditorCell>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut editor: ResMut<Editor>,
+    mut level: ResMut<Level>,
+) {
+    let Ok(cell) = cells.get(trigger.target()) else {
+        return;
+    };
+
+    let tool = if mouse_input.pressed(MouseButton::Left) && editor.tool.paints() {
+        editor.tool
+    } else if mouse_input.pressed(MouseButton::Right) {
+        Tool::Erase
+    } else {
+        return;
+    };
+
+    let mut edited = level.clone();
+    editor.apply(&mut edited, tool, cell.as_ivec2());
+    editor.commit(&mut level, edited);
+}
+
+fn draw_level(
+    mut commands: Commands,
+    canvas: Query<Entity, With<EditorCanvas>>,
+    level: Res<Level>,
+    editor: Res<Editor>,
+    size: Res<CellSize>,
+    theme: Res<Theme>,
+) {
+    for entity in &canvas {
+        commands.entity(entity).despawn();
+    }
+
+    for y in 0..level.size.y {
+        for x in 0..level.size.x {
+            commands.spawn((
+                StateScoped(Screen::Editor),
+                EditorCanvas,
+                EditorCell,
+                board_cell(x, y, **size, &theme),
+            ));
+        }
+    }
+
+    for wall in &level.walls {
+        commands.spawn(overlay(wall.as_vec2(), **size, 1.0, theme.wall));
+    }
+
+    for (index, portal) in level.portals.iter().enumerate() {
+        for end in portal {
+            commands.spawn(overlay(
+                end.as_vec2(),
+                **size,
+                1.0,
+                Level::portal_color(index),
+            ));
+        }
+    }
+
+    if let Some(pending) = editor.pending_portal {
+        commands.spawn(overlay(
+            pending.as_vec2(),
+            **size,
+            0.6,
+            Level::portal_color(level.portals.len()),
+        ));
+    }
+
+    for tile in &level.tiles {
+        commands.spawn(overlay(tile.cell.as_vec2(), **size, 1.0, tile.kind.color()));
+    }
+
+    commands.spawn((
+        overlay(level.start.as_vec2(), **size, 0.8, START_COLOR),
+        children![(
+            Pickable::IGNORE,
+            Transform::from_translation(Vec3::from((level.direction.to_vec() * 0.4, 0.1)))
+                .with_scale(Vec3::splat(0.3)),
+            Sprite::from_color(Color::BLACK, Vec2::ONE),
+        )],
+    ));
+}
+
+fn update_editor_hud(
+    mut hud: Single<&mut Text, With<EditorHud>>,
+    level: Res<Level>,
+    editor: Res<Editor>,
+) {
+    hud.0 = format!(
+        "Tool: {}  |  Size: {}x{}  |  Lives: {}  Food: {}  Difficulty: {}\n\
+         1 Wall  2 Portal  3 Boost  4 Slow  5 Start  0 Erase  |  Right click: Erase\n\
+         Arrows: Resize  R: Rotate start  L: Lives  F: Food  D: Difficulty\n\
+         Ctrl+Z: Undo  Ctrl+Y: Redo  Ctrl+S: Save  P: Play  Esc: Exit\n{}",
+        editor.tool.label(),
+        level.size.x,
+        level.size.y,
+        if level.rules.lives { "On" } else { "Off" },
+        level.rules.food_count,
+        level.rules.difficulty.label(),
+        editor.status,
+    );
+}
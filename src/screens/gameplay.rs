@@ -1,8 +1,12 @@
-use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, window::WindowOccluded};
 
 use crate::{
-    game::{GameState, InGame},
+    game::{
+        GameState, InGame, countdown,
+        events::{BoardCleared, CatCrashed, GoalReached, RunEnded},
+    },
     screens::Screen,
+    settings::Settings,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -12,7 +16,18 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         (
             pause_game.run_if(in_state(Screen::Gameplay).and(input_just_pressed(KeyCode::Escape))),
+            pause_on_focus_loss.run_if(
+                in_state(Screen::Gameplay).and(|settings: Res<Settings>| settings.auto_pause),
+            ),
             unpause_game.run_if(in_state(Screen::Pause).and(input_just_pressed(KeyCode::Escape))),
+            end_game.run_if(
+                in_state(Screen::Gameplay).and(
+                    on_event::<CatCrashed>
+                        .or(on_event::<BoardCleared>)
+                        .or(on_event::<GoalReached>)
+                        .or(on_event::<RunEnded>),
+                ),
+            ),
         ),
     );
 }
@@ -20,8 +35,9 @@ pub(super) fn plugin(app: &mut App) {
 fn start_game(
     mut next_gameplay: ResMut<NextState<GameState>>,
     mut next_in_game: ResMut<NextState<InGame>>,
+    settings: Res<Settings>,
 ) {
-    next_gameplay.set(GameState::Run);
+    next_gameplay.set(countdown::start_state(&settings));
     next_in_game.set(InGame::True);
 }
 
@@ -29,6 +45,32 @@ fn pause_game(mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Pause);
 }
 
+fn pause_on_focus_loss(
+    window: Single<&Window>,
+    mut occluded: EventReader<WindowOccluded>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let occluded = occluded.read().last().is_some_and(|event| event.occluded);
+    if !window.focused || occluded {
+        next_screen.set(Screen::Pause);
+    }
+}
+
 fn unpause_game(mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Gameplay);
 }
+
+fn end_game(
+    mut crashed: EventReader<CatCrashed>,
+    mut board_cleared: EventReader<BoardCleared>,
+    mut goal_reached: EventReader<GoalReached>,
+    mut run_ended: EventReader<RunEnded>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let cleared = board_cleared.read().count() > 0;
+    let reached = goal_reached.read().count() > 0;
+    let ended = run_ended.read().count() > 0;
+    if crashed.read().any(|event| event.fatal) || cleared || reached || ended {
+        next_screen.set(Screen::Over);
+    }
+}
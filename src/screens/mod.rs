@@ -1,3 +1,4 @@
+mod editor;
 mod gameplay;
 mod over;
 mod pause;
@@ -7,7 +8,13 @@ use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
-    app.add_plugins((title::plugin, gameplay::plugin, pause::plugin, over::plugin));
+    app.add_plugins((
+        title::plugin,
+        gameplay::plugin,
+        pause::plugin,
+        over::plugin,
+        editor::plugin,
+    ));
 }
 
 #[derive(States, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
@@ -18,4 +25,5 @@ pub enum Screen {
     Gameplay,
     Pause,
     Over,
+    Editor,
 }
@@ -1,6 +1,11 @@
 use bevy::prelude::*;
 
-use crate::{game::GameState, menus::Menu, screens::Screen};
+use crate::{
+    game::{GameState, countdown},
+    menus::Menu,
+    screens::Screen,
+    settings::Settings,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Pause), open_pause_menu);
@@ -18,7 +23,8 @@ fn open_pause_menu(
 fn close_pause_menu(
     mut next_menu: ResMut<NextState<Menu>>,
     mut next_gameplay: ResMut<NextState<GameState>>,
+    settings: Res<Settings>,
 ) {
     next_menu.set(Menu::None);
-    next_gameplay.set(GameState::Run);
+    next_gameplay.set(countdown::start_state(&settings));
 }
@@ -3,6 +3,7 @@ use bevy::prelude::*;
 use crate::{
     game::{GameState, InGame},
     menus::Menu,
+    profiles::ActiveProfile,
     screens::Screen,
 };
 
@@ -15,8 +16,13 @@ fn open_main_menu(
     mut next_menu: ResMut<NextState<Menu>>,
     mut next_game_state: ResMut<NextState<GameState>>,
     mut next_in_game: ResMut<NextState<InGame>>,
+    active_profile: Res<ActiveProfile>,
 ) {
-    next_menu.set(Menu::Main);
+    next_menu.set(if active_profile.is_some() {
+        Menu::Main
+    } else {
+        Menu::Profiles
+    });
     next_game_state.set(GameState::None);
     next_in_game.set(InGame::False);
 }
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    profiles::{ActiveProfile, load_profile_data},
+    storage,
+};
+
+pub const MAX_FOOD_COUNT: usize = 5;
+pub const MAX_COUNTDOWN: u32 = 5;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Settings>();
+    app.add_systems(
+        PreUpdate,
+        load_profile_data::<Settings>(SETTINGS_FILE).run_if(resource_changed::<ActiveProfile>),
+    );
+    app.add_systems(
+        Update,
+        save_settings.run_if(resource_changed::<Settings>.and(not(resource_added::<Settings>))),
+    );
+}
+
+#[derive(Resource, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct Settings {
+    pub lives: bool,
+    pub food_count: usize,
+    pub smooth_movement: bool,
+    pub telemetry: bool,
+    pub difficulty: Difficulty,
+    pub countdown: u32,
+    pub auto_pause: bool,
+    pub key_bindings: KeyBindings,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            up: KeyCode::ArrowUp,
+            down: KeyCode::ArrowDown,
+            left: KeyCode::ArrowLeft,
+            right: KeyCode::ArrowRight,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Insane,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Insane,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Insane => "Insane",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Insane,
+            Difficulty::Insane => Difficulty::Easy,
+        }
+    }
+
+    pub fn tick_seconds(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.15,
+            Difficulty::Normal => 0.1,
+            Difficulty::Hard => 0.07,
+            Difficulty::Insane => 0.05,
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            lives: false,
+            food_count: 1,
+            smooth_movement: false,
+            telemetry: false,
+            difficulty: Difficulty::default(),
+            countdown: 3,
+            auto_pause: true,
+            key_bindings: KeyBindings::default(),
+        }
+    }
+}
+
+fn save_settings(settings: Res<Settings>, profile: Res<ActiveProfile>) {
+    storage::save(**profile, SETTINGS_FILE, &*settings);
+}
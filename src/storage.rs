@@ -0,0 +1,74 @@
+use std::{fs, path::PathBuf};
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("longcat"))
+}
+
+pub fn profile_dir(profile: Option<u32>) -> Option<PathBuf> {
+    let dir = data_dir()?;
+    Some(match profile {
+        Some(id) => dir.join("profiles").join(id.to_string()),
+        None => dir,
+    })
+}
+
+pub fn load<T: DeserializeOwned>(profile: Option<u32>, name: &str) -> Option<T> {
+    let path = profile_dir(profile)?.join(name);
+    let contents = fs::read_to_string(&path).ok()?;
+    serde_json::from_str(&contents)
+        .inspect_err(|e| warn!("failed to parse {}: {e}", path.display()))
+        .ok()
+}
+
+pub fn save<T: Serialize>(profile: Option<u32>, name: &str, value: &T) {
+    let Some(dir) = profile_dir(profile) else {
+        return;
+    };
+
+    let path = dir.join(name);
+    let result = fs::create_dir_all(path.parent().unwrap_or(&dir))
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string_pretty(value).map_err(|e| e.to_string()))
+        .and_then(|contents| fs::write(&path, contents).map_err(|e| e.to_string()));
+
+    if let Err(e) = result {
+        warn!("failed to save {}: {e}", path.display());
+    }
+}
+
+pub fn save_text(profile: Option<u32>, name: &str, contents: &str) -> Option<PathBuf> {
+    let dir = profile_dir(profile)?;
+    let path = dir.join(name);
+    fs::create_dir_all(path.parent().unwrap_or(&dir))
+        .and_then(|_| fs::write(&path, contents))
+        .inspect_err(|e| warn!("failed to save {}: {e}", path.display()))
+        .ok()?;
+    Some(path)
+}
+
+pub fn remove(profile: Option<u32>, name: &str) {
+    let Some(path) = profile_dir(profile).map(|dir| dir.join(name)) else {
+        return;
+    };
+
+    if let Err(e) = fs::remove_file(&path)
+        && path.exists()
+    {
+        warn!("failed to remove {}: {e}", path.display());
+    }
+}
+
+pub fn remove_profile(id: u32) {
+    let Some(dir) = profile_dir(Some(id)) else {
+        return;
+    };
+
+    if let Err(e) = fs::remove_dir_all(&dir)
+        && dir.exists()
+    {
+        warn!("failed to remove {}: {e}", dir.display());
+    }
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Theme>();
+    app.add_systems(Update, apply_clear_color.run_if(resource_changed::<Theme>));
+}
+
+#[derive(Resource, Asset, TypePath, Clone, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct Theme {
+    pub background: Color,
+    pub board_light: Color,
+    pub board_dark: Color,
+    pub wall: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            background: Color::BLACK,
+            board_light: Color::srgb_u8(0x10, 0x10, 0x10),
+            board_dark: Color::srgb_u8(0x20, 0x20, 0x20),
+            wall: Color::srgb_u8(0x60, 0x48, 0x38),
+        }
+    }
+}
+
+impl Theme {
+    pub fn board_color(&self, x: i32, y: i32) -> Color {
+        if (x + y) % 2 == 0 {
+            self.board_light
+        } else {
+            self.board_dark
+        }
+    }
+}
+
+fn apply_clear_color(mut clear_color: ResMut<ClearColor>, theme: Res<Theme>) {
+    clear_color.0 = theme.background;
+}
@@ -0,0 +1,107 @@
+use bevy::{
+    ecs::{spawn::SpawnWith, system::IntoSystem},
+    input::keyboard::KeyboardInput,
+    input_focus::{FocusedInput, InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use crate::ui::button;
+
+#[derive(Component, Debug)]
+struct Dialog {
+    previous_focus: Option<Entity>,
+}
+
+pub fn confirm_dialog<M: 'static>(
+    message: &str,
+    on_confirm: impl IntoSystem<(), (), M> + Copy + Send + Sync + 'static,
+) -> impl Bundle {
+    let message = message.to_string();
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+        GlobalZIndex(2),
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            let dialog = parent.target_entity();
+            let confirm = parent
+                .spawn(button("Yes"))
+                .observe(
+                    move |_: Trigger<FocusedInput<KeyboardInput>>,
+                          keyboard_input: Res<ButtonInput<KeyCode>>,
+                          mut commands: Commands| {
+                        if keyboard_input.just_pressed(KeyCode::Enter) {
+                            commands.run_system_cached(on_confirm);
+                            commands.run_system_cached_with(close_dialog, dialog);
+                        }
+                    },
+                )
+                .id();
+            let cancel = parent
+                .spawn(button("No"))
+                .observe(
+                    move |_: Trigger<FocusedInput<KeyboardInput>>,
+                          keyboard_input: Res<ButtonInput<KeyCode>>,
+                          mut commands: Commands| {
+                        if keyboard_input.just_pressed(KeyCode::Enter) {
+                            commands.run_system_cached_with(close_dialog, dialog);
+                        }
+                    },
+                )
+                .id();
+
+            let world = parent.world_mut();
+            world
+                .resource_mut::<DirectionalNavigationMap>()
+                .add_looping_edges(&[cancel, confirm], CompassOctant::South);
+            let mut input_focus = world.resource_mut::<InputFocus>();
+            let previous_focus = input_focus.get();
+            input_focus.set(cancel);
+
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(20.0)),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb_u8(0x18, 0x18, 0x18)),
+                    children![(
+                        Text::new(message),
+                        TextFont::from_font_size(30.0),
+                        TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+                    )],
+                ))
+                .add_children(&[confirm, cancel]);
+            parent
+                .world_mut()
+                .entity_mut(dialog)
+                .insert(Dialog { previous_focus });
+        })),
+    )
+}
+
+fn close_dialog(
+    In(dialog): In<Entity>,
+    mut commands: Commands,
+    dialogs: Query<&Dialog>,
+    mut input_focus: ResMut<InputFocus>,
+) {
+    let Ok(state) = dialogs.get(dialog) else {
+        return;
+    };
+
+    match state.previous_focus {
+        Some(previous) => input_focus.set(previous),
+        None => input_focus.clear(),
+    }
+    commands.entity(dialog).despawn();
+}
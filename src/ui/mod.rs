@@ -1,3 +1,7 @@
 mod button;
+mod dialog;
+mod text_input;
 
 pub use button::button;
+pub use dialog::confirm_dialog;
+pub use text_input::{TextInput, edit_text_input, text_input};
@@ -0,0 +1,67 @@
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    input_focus::FocusedInput,
+    prelude::*,
+};
+
+#[derive(Component, Debug)]
+pub struct TextInput {
+    pub value: String,
+    max_length: usize,
+}
+
+pub fn text_input(value: &str, max_length: usize) -> impl Bundle {
+    (
+        TextInput {
+            value: value.to_string(),
+            max_length,
+        },
+        Button,
+        Node {
+            width: Val::Percent(100.0),
+            padding: UiRect::axes(Val::Px(20.0), Val::Px(10.0)),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::NONE),
+        children![(
+            Text::new(format!("{value}_")),
+            TextFont::from_font_size(40.0),
+            TextColor(Color::srgb_u8(0xa0, 0xa0, 0xa0)),
+        )],
+    )
+}
+
+pub fn edit_text_input(
+    trigger: Trigger<FocusedInput<KeyboardInput>>,
+    mut inputs: Query<(&mut TextInput, &Children)>,
+    mut text: Query<&mut Text>,
+) {
+    let Ok((mut input, children)) = inputs.get_mut(trigger.target()) else {
+        return;
+    };
+
+    let event = &trigger.event().input;
+    if event.state != ButtonState::Pressed {
+        return;
+    }
+
+    if event.logical_key == Key::Backspace {
+        input.value.pop();
+    } else if let Some(typed) = &event.text {
+        for character in typed.chars().filter(|character| !character.is_control()) {
+            if input.value.chars().count() < input.max_length {
+                input.value.push(character);
+            }
+        }
+    }
+
+    if let Ok(mut text) = text.get_mut(children[0]) {
+        text.0 = format!("{}_", input.value);
+    }
+}